@@ -1,5 +1,8 @@
 #![no_std]
-use aidoku::{prelude::*, DeepLinkHandler, Home, ImageRequestProvider, ListingProvider, Source};
+use aidoku::{
+	prelude::*, DeepLinkHandler, DynamicFilters, Home, ImageRequestProvider, ListingProvider,
+	PageImageProcessor, Source,
+};
 use mangabox::{Impl, MangaBox, Params};
 
 const BASE_URL: &str = "https://www.mangakakalot.gg";
@@ -23,6 +26,8 @@ register_source!(
 	MangaBox<MangaKakalot>,
 	ListingProvider,
 	Home,
+	DynamicFilters,
 	ImageRequestProvider,
+	PageImageProcessor,
 	DeepLinkHandler
 );
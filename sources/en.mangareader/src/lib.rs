@@ -0,0 +1,31 @@
+#![no_std]
+use aidoku::{alloc::borrow::Cow, prelude::*, Source};
+use mangareader::{Impl, MangaReader, Params};
+
+const BASE_URL: &str = "https://mangareader.to";
+
+struct MangaReaderEn;
+
+impl Impl for MangaReaderEn {
+	fn new() -> Self {
+		Self
+	}
+
+	fn params(&self) -> Params {
+		Params {
+			base_url: BASE_URL.into(),
+			page_selector: ".container-reader-chapter > div.iv-card".into(),
+			languages: Cow::Borrowed(&["en"]),
+			..Default::default()
+		}
+	}
+}
+
+register_source!(
+	MangaReader<MangaReaderEn>,
+	ListingProvider,
+	Home,
+	ImageRequestProvider,
+	PageImageProcessor,
+	DeepLinkHandler
+);
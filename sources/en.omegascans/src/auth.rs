@@ -0,0 +1,119 @@
+use aidoku::{
+	alloc::{String, Vec},
+	imports::{
+		defaults::{defaults_get, defaults_set, DefaultValue},
+		net::Request,
+	},
+	prelude::*,
+	AidokuError, Result,
+};
+use serde::Serialize;
+
+use crate::BASE_API_URL;
+
+static EMAIL_KEY: &str = "login.username";
+static COOKIE_KEY: &str = "login.cookie";
+
+#[derive(Serialize)]
+struct LoginBody<'a> {
+	email: &'a str,
+	password: &'a str,
+}
+
+pub fn login(email: &str, password: &str) -> Result<bool> {
+	let url = format!("{}/login", BASE_API_URL);
+	let body = serde_json::to_string(&LoginBody { email, password })
+		.map_err(|_| AidokuError::message("Unable to encode login details"))?;
+	let res = Request::post(&url)?
+		.header("Content-Type", "application/json")
+		.header("Accept", "application/json")
+		.body(&body)
+		.send()?;
+
+	if res.status_code() != 200 {
+		return Ok(false);
+	}
+	let Some(cookie) = res
+		.get_header("Set-Cookie")
+		.map(|header| cookie_pairs(&header))
+		.filter(|cookie| !cookie.is_empty())
+	else {
+		return Ok(false);
+	};
+
+	// make sure the session is accepted before keeping it
+	let logged_in = Request::get(format!("{}/user", BASE_API_URL))?
+		.header("Cookie", &cookie)
+		.header("Accept", "application/json")
+		.send()
+		.is_ok_and(|res| res.status_code() == 200);
+	if logged_in {
+		defaults_set(COOKIE_KEY, DefaultValue::String(cookie));
+	}
+	Ok(logged_in)
+}
+
+// keeps only the name=value pairs from a (possibly combined) set-cookie header
+fn cookie_pairs(header: &str) -> String {
+	const ATTRIBUTES: [&str; 6] = [
+		"expires", "max-age", "domain", "path", "samesite", "priority",
+	];
+	header
+		.split(',')
+		// attributes follow the first semicolon, and expiry dates contain commas
+		.filter_map(|part| part.split(';').next())
+		.map(str::trim)
+		.filter(|pair| {
+			pair.split_once('=').is_some_and(|(name, _)| {
+				!name.is_empty()
+					&& !name.contains(' ')
+					&& !ATTRIBUTES.iter().any(|attr| name.eq_ignore_ascii_case(attr))
+			})
+		})
+		.collect::<Vec<_>>()
+		.join("; ")
+}
+
+pub fn logout() {
+	defaults_set(COOKIE_KEY, DefaultValue::Null);
+}
+
+pub fn is_logged_in() -> bool {
+	defaults_get::<String>(EMAIL_KEY).is_some() && defaults_get::<String>(COOKIE_KEY).is_some()
+}
+
+pub trait AuthedRequest {
+	fn authed(self) -> Self;
+}
+
+impl AuthedRequest for Request {
+	fn authed(self) -> Self {
+		let cookie = defaults_get::<String>(COOKIE_KEY);
+		if let Some(cookie) = cookie {
+			self.header("Cookie", &cookie)
+		} else {
+			self
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use aidoku_test::aidoku_test;
+
+	#[aidoku_test]
+	fn cookie_pairs_strip_attributes() {
+		assert_eq!(
+			cookie_pairs("ts-session=abc123; Path=/; HttpOnly; Secure; SameSite=None"),
+			"ts-session=abc123"
+		);
+		assert_eq!(
+			cookie_pairs(
+				"ts-session=abc; Expires=Wed, 21 Oct 2026 07:28:00 GMT; Path=/, remember=xyz; Max-Age=3600"
+			),
+			"ts-session=abc; remember=xyz"
+		);
+		assert_eq!(cookie_pairs("Path=/; HttpOnly"), "");
+	}
+}
@@ -0,0 +1,111 @@
+use crate::{parser, OmegaScans, BASE_URL};
+use aidoku::{
+	alloc::{String, Vec},
+	imports::{html::Element, net::Request},
+	prelude::*,
+	Home, HomeComponent, HomeComponentValue, HomeLayout, Listing, Manga, Result,
+};
+
+impl Home for OmegaScans {
+	fn get_home(&self) -> Result<HomeLayout> {
+		let sections = [
+			("latest", "Latest Updates"),
+			("popular", "Popular"),
+			("newest", "Newest"),
+		];
+
+		let responses = Request::send_all(
+			sections
+				.iter()
+				.map(|(id, _)| Request::get(parser::listing_url(id, 1)))
+				.collect::<core::result::Result<Vec<_>, _>>()?,
+		);
+
+		let mut components = Vec::new();
+
+		if let Some(featured) = get_featured().filter(|entries| !entries.is_empty()) {
+			components.push(HomeComponent {
+				title: None,
+				subtitle: None,
+				value: HomeComponentValue::BigScroller {
+					entries: featured,
+					auto_scroll_interval: Some(8.0),
+				},
+			});
+		}
+
+		for ((id, name), response) in sections.into_iter().zip(responses) {
+			let Ok(entries) = response
+				.map_err(Into::into)
+				.and_then(|response| parser::parse_manga(BASE_URL, response))
+				.map(|result| result.entries)
+			else {
+				continue;
+			};
+			let listing = Some(Listing {
+				id: id.into(),
+				name: name.into(),
+				..Default::default()
+			});
+			let entries = entries.into_iter().map(Into::into).collect();
+			components.push(HomeComponent {
+				title: Some(name.into()),
+				subtitle: None,
+				value: if id == "popular" {
+					HomeComponentValue::MangaList {
+						ranking: true,
+						page_size: Some(5),
+						entries,
+						listing,
+					}
+				} else {
+					HomeComponentValue::Scroller { entries, listing }
+				},
+			});
+		}
+
+		Ok(HomeLayout { components })
+	}
+}
+
+// the featured carousel is only part of the home page html
+fn get_featured() -> Option<Vec<Manga>> {
+	let html = Request::get(BASE_URL).ok()?.html().ok()?;
+	let slides = html.select(".swiper-slide:not(.swiper-slide-duplicate)")?;
+	let mut entries: Vec<Manga> = Vec::new();
+	for slide in slides {
+		let Some(manga) = parse_featured_slide(&slide) else {
+			continue;
+		};
+		if !entries.iter().any(|m| m.key == manga.key) {
+			entries.push(manga);
+		}
+	}
+	Some(entries)
+}
+
+fn parse_featured_slide(slide: &Element) -> Option<Manga> {
+	let url = slide.select_first("a[href*=/series/]")?.attr("abs:href")?;
+	let key: String = url
+		.strip_prefix(BASE_URL)?
+		.strip_prefix("/series/")?
+		.split('/')
+		.next()?
+		.into();
+	let img = slide.select_first("img");
+	let title = slide
+		.select_first("h1, h2, h3")
+		.and_then(|el| el.text())
+		.or_else(|| img.as_ref().and_then(|el| el.attr("alt")))?;
+	Some(Manga {
+		key,
+		title,
+		cover: img.and_then(|el| el.attr("abs:src")),
+		url: Some(url),
+		tags: slide
+			.select(".badge, [class*=tag]")
+			.map(|els| els.filter_map(|el| el.text()).collect::<Vec<_>>())
+			.filter(|tags| !tags.is_empty()),
+		..Default::default()
+	})
+}
@@ -1,12 +1,15 @@
 #![no_std]
 use aidoku::{
-	alloc::{String, Vec},
+	alloc::{vec, String, Vec},
 	imports::net::Request,
 	prelude::*,
-	Chapter, DeepLinkHandler, DeepLinkResult, FilterValue, ImageRequestProvider, Listing,
-	ListingProvider, Manga, MangaPageResult, Page, PageContext, Result, Source,
+	BasicLoginHandler, Chapter, DeepLinkHandler, DeepLinkResult, DynamicFilters, Filter,
+	FilterValue, ImageRequestProvider, Listing, ListingProvider, Manga, MangaPageResult,
+	MultiSelectFilter, NotificationHandler, Page, PageContext, Result, Source,
 };
 
+mod auth;
+mod home;
 mod parser;
 
 const BASE_URL: &str = "https://omegascans.org";
@@ -31,10 +34,10 @@ impl Source for OmegaScans {
 	fn get_manga_update(
 		&self,
 		manga: Manga,
-		_needs_details: bool,
-		_needs_chapters: bool,
+		needs_details: bool,
+		needs_chapters: bool,
 	) -> Result<Manga> {
-		parser::parse_manga_details(&String::from(BASE_URL), manga.key)
+		parser::parse_manga_update(BASE_URL, manga, needs_details, needs_chapters)
 	}
 
 	fn get_page_list(&self, manga: Manga, chapter: Chapter) -> Result<Vec<Page>> {
@@ -48,6 +51,44 @@ impl ListingProvider for OmegaScans {
 	}
 }
 
+impl DynamicFilters for OmegaScans {
+	fn get_dynamic_filters(&self) -> Result<Vec<Filter>> {
+		let tags = parser::get_tags();
+		if tags.is_empty() {
+			return Ok(Vec::new());
+		}
+		let (options, ids) = tags
+			.into_iter()
+			.map(|tag| (tag.name.into(), format!("{}", tag.id).into()))
+			.unzip();
+		Ok(vec![MultiSelectFilter {
+			id: "genre".into(),
+			title: Some("Genres".into()),
+			is_genre: true,
+			options,
+			ids: Some(ids),
+			..Default::default()
+		}
+		.into()])
+	}
+}
+
+impl BasicLoginHandler for OmegaScans {
+	fn handle_basic_login(&self, _key: String, username: String, password: String) -> Result<bool> {
+		auth::login(&username, &password)
+	}
+}
+
+impl NotificationHandler for OmegaScans {
+	fn handle_notification(&self, notification: String) {
+		// handle log out
+		if notification == "login" && !auth::is_logged_in() {
+			// if the username/password default keys were removed, we should remove the cookie key
+			auth::logout();
+		}
+	}
+}
+
 impl ImageRequestProvider for OmegaScans {
 	fn get_image_request(&self, url: String, _context: Option<PageContext>) -> Result<Request> {
 		Ok(Request::get(&url)?.header("Referer", BASE_URL))
@@ -55,9 +96,43 @@ impl ImageRequestProvider for OmegaScans {
 }
 
 impl DeepLinkHandler for OmegaScans {
-	fn handle_deep_link(&self, _url: String) -> Result<Option<DeepLinkResult>> {
-		Ok(None)
+	fn handle_deep_link(&self, url: String) -> Result<Option<DeepLinkResult>> {
+		let Some(path) = url.strip_prefix(BASE_URL) else {
+			return Ok(None);
+		};
+
+		const SERIES_PATH: &str = "/series/";
+
+		let Some(path) = path.strip_prefix(SERIES_PATH) else {
+			return Ok(None);
+		};
+		let mut segments = path
+			.split(['?', '#'])
+			.next()
+			.unwrap_or_default()
+			.split('/')
+			.filter(|segment| !segment.is_empty());
+
+		match (segments.next(), segments.next()) {
+			// ex: https://omegascans.org/series/the-disciple-wants-to-kill-me/chapter-1
+			(Some(manga_key), Some(key)) => Ok(Some(DeepLinkResult::Chapter {
+				manga_key: manga_key.into(),
+				key: key.into(),
+			})),
+			// ex: https://omegascans.org/series/the-disciple-wants-to-kill-me
+			(Some(key), None) => Ok(Some(DeepLinkResult::Manga { key: key.into() })),
+			_ => Ok(None),
+		}
 	}
 }
 
-register_source!(OmegaScans, ListingProvider, ImageRequestProvider, DeepLinkHandler);
+register_source!(
+	OmegaScans,
+	ListingProvider,
+	Home,
+	DynamicFilters,
+	BasicLoginHandler,
+	NotificationHandler,
+	ImageRequestProvider,
+	DeepLinkHandler
+);
@@ -1,19 +1,36 @@
 use aidoku::{
 	alloc::{vec, String, Vec},
-	imports::net::Request,
+	helpers::uri::QueryParameters,
+	imports::{
+		defaults::{defaults_get, defaults_set, DefaultValue},
+		html::{Element, Html},
+		net::Request,
+		std::{current_date, send_partial_result},
+	},
 	prelude::*,
 	Chapter, ContentRating, FilterValue, Listing, Manga, MangaPageResult, MangaStatus, Page,
 	PageContent, Result, Viewer,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::BASE_API_URL;
+use crate::{
+	auth::{self, AuthedRequest},
+	BASE_API_URL,
+};
+
+const CHAPTERS_PER_PAGE: i32 = 30;
+const COMIC_TYPE: &str = "Comic";
+const MANGA_PER_PAGE: i32 = 10;
+const NOVEL_TYPE: &str = "Novel";
+const SHOW_LOCKED_KEY: &str = "showLocked";
+const TAGS_KEY: &str = "tags";
+const TAGS_MAX_AGE: i64 = 60 * 60 * 24; // one day
 
 #[derive(Deserialize)]
 struct ApiResponse {
 	data: Vec<MangaData>,
 	#[serde(default)]
-	total: Option<i32>,
+	meta: Option<MetaData>,
 }
 
 #[derive(Deserialize)]
@@ -34,6 +51,8 @@ struct SeriesResponse {
 	series_slug: String,
 	status: String,
 	tags: Vec<TagData>,
+	#[serde(default)]
+	series_type: String,
 }
 
 #[derive(Deserialize)]
@@ -41,6 +60,18 @@ struct TagData {
 	name: String,
 }
 
+#[derive(Deserialize, Serialize)]
+pub struct Tag {
+	pub id: i32,
+	pub name: String,
+}
+
+#[derive(Deserialize, Serialize)]
+struct SavedTags {
+	fetched_at: i64,
+	tags: Vec<Tag>,
+}
+
 #[derive(Deserialize)]
 struct ChapterResponse {
 	data: Vec<ChapterData>,
@@ -54,9 +85,32 @@ struct ChapterData {
 	created_at: String,
 }
 
+#[derive(Deserialize)]
+struct ChapterDetailsResponse {
+	chapter: ChapterDetails,
+	#[serde(default)]
+	paywall: bool,
+}
+
+#[derive(Deserialize)]
+struct ChapterDetails {
+	#[serde(default)]
+	chapter_data: Option<ChapterImageData>,
+	// novel chapters have their text as html instead of images
+	#[serde(default)]
+	chapter_content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ChapterImageData {
+	#[serde(default)]
+	images: Vec<String>,
+}
+
 #[derive(Deserialize)]
 struct MetaData {
-	first_page: i32,
+	#[serde(default)]
+	current_page: Option<i32>,
 	last_page: i32,
 }
 
@@ -67,155 +121,267 @@ pub fn parse_manga_list(
 	page: i32,
 ) -> Result<MangaPageResult> {
 	let search_query = query.unwrap_or_default();
-	let mut genres = String::new();
+	let mut tag_ids: Vec<String> = Vec::new();
+	let mut series_type = String::from(COMIC_TYPE);
+	let mut order_by = "total_views";
+	let mut ascending = false;
 
 	for filter in filters {
 		match filter {
-			FilterValue::Select { id, value, .. } => {
-				if id == "genre" && value != "All" {
-					// Map genre name to ID for the API
-					let genre_id = match value.as_str() {
-						"Romance" => "1",
-						"Drama" => "2",
-						"Fantasy" => "3",
-						"Hardcore" => "4",
-						"SM" => "5",
-						"Harem" => "8",
-						"Hypnosis" => "9",
-						"Novel Adaptation" => "10",
-						"Netori" => "11",
-						"Netorare" => "12",
-						"Isekai" => "13",
-						"Yuri" => "14",
-						"MILF" => "16",
-						"Office" => "17",
-						"Short Story" => "18",
-						"Comedy" => "19",
-						"Campus" => "20",
-						"Crime" => "21",
-						"Revenge" => "22",
-						"Supernatural" => "23",
-						"Action" => "24",
-						"Military" => "25",
-						"Cohabitation" => "27",
-						"Training" => "28",
-						"Ability" => "29",
-						_ => continue,
-					};
-					genres.push_str(genre_id);
-					genres.push(',');
-				}
+			FilterValue::Sort {
+				index,
+				ascending: asc,
+				..
+			} => {
+				order_by = match index {
+					0 => "latest",
+					1 => "total_views",
+					2 => "created_at",
+					3 => "title",
+					_ => "total_views",
+				};
+				ascending = asc;
+			}
+			FilterValue::MultiSelect { id, included, .. } if id == "genre" => {
+				tag_ids.extend(
+					included
+						.iter()
+						.filter_map(|value| tag_id(value))
+						.map(|id| format!("{id}")),
+				);
+			}
+			// tag searches from a series page
+			FilterValue::Select { id, value } if id == "genre" => {
+				tag_ids.extend(tag_id(&value).map(|id| format!("{id}")));
+			}
+			FilterValue::Select { id, value } if id == "type" => {
+				series_type = value;
 			}
 			_ => continue,
 		}
 	}
 
-	if !genres.is_empty() {
-		genres.pop();
+	let url = query_url(
+		&search_query,
+		order_by,
+		ascending,
+		&series_type,
+		&tag_ids,
+		page,
+	);
+	let response = Request::get(&url)?.send()?;
+	parse_manga(&base_url, response)
+}
+
+fn query_url(
+	query: &str,
+	order_by: &str,
+	ascending: bool,
+	series_type: &str,
+	tag_ids: &[String],
+	page: i32,
+) -> String {
+	let mut qs = QueryParameters::new();
+	qs.push("query_string", Some(query));
+	qs.push("order", Some(if ascending { "asc" } else { "desc" }));
+	qs.push("orderBy", Some(order_by));
+	qs.push("series_type", Some(series_type));
+	qs.push("page", Some(&format!("{page}")));
+	qs.push("perPage", Some(&format!("{MANGA_PER_PAGE}")));
+	qs.push("tags_ids", Some(&format!("[{}]", tag_ids.join(","))));
+	qs.push("adult", Some("true"));
+	format!("{}/query?{qs}", BASE_API_URL)
+}
+
+// the genre filter options, refreshed from the api once the saved ones are a day old
+pub fn get_tags() -> Vec<Tag> {
+	let saved = saved_tags();
+	let now = current_date() as i64;
+	match saved {
+		Some(saved) if now - saved.fetched_at < TAGS_MAX_AGE => saved.tags,
+		saved => fetch_tags(now)
+			.ok()
+			.or_else(|| saved.map(|saved| saved.tags))
+			.unwrap_or_default(),
+	}
+}
+
+fn fetch_tags(now: i64) -> Result<Vec<Tag>> {
+	let url = format!("{}/tags", BASE_API_URL);
+	let tags = Request::get(&url)?.send()?.get_json::<Vec<Tag>>()?;
+	let saved = SavedTags {
+		fetched_at: now,
+		tags,
+	};
+	if let Ok(json) = serde_json::to_string(&saved) {
+		defaults_set(TAGS_KEY, DefaultValue::String(json));
 	}
+	Ok(saved.tags)
+}
 
-	let url = format!("{}/query?query_string={}&order=desc&orderBy=total_views&series_type=Comic&page={}&perPage=10&tags_ids=[{}]&adult=true", BASE_API_URL, search_query, page, genres);
-	let response = Request::get(&url)?.send()?;
-	let manga = parse_manga(&base_url, response)?;
-	let has_next_page = !manga.is_empty();
+fn saved_tags() -> Option<SavedTags> {
+	defaults_get::<String>(TAGS_KEY).and_then(|json| serde_json::from_str(&json).ok())
+}
 
-	Ok(MangaPageResult {
-		entries: manga,
-		has_next_page,
-	})
+// genre filter values are tag ids, while tapped series tags only have their name
+fn tag_id(value: &str) -> Option<i32> {
+	let value = value.trim();
+	get_tags()
+		.into_iter()
+		.find(|tag| format!("{}", tag.id) == value || tag.name.eq_ignore_ascii_case(value))
+		.map(|tag| tag.id)
 }
 
-pub fn parse_manga_listing(
-	base_url: String,
-	listing: Listing,
-	page: i32,
-) -> Result<MangaPageResult> {
-	let list_query = match listing.id.as_str() {
+pub fn listing_url(listing_id: &str, page: i32) -> String {
+	let list_query = match listing_id {
 		"latest" => "latest",
 		"popular" => "total_views",
 		"newest" => "created_at",
 		"alphabetical" => "title",
+		"novels" => "latest",
 		_ => "latest",
 	};
-	let url = format!("{}/query?query_string=&order=desc&orderBy={}&series_type=Comic&page={}&perPage=10&tags_ids=[]&adult=true", BASE_API_URL, list_query, page);
+	let series_type = if listing_id == "novels" {
+		NOVEL_TYPE
+	} else {
+		COMIC_TYPE
+	};
+	query_url("", list_query, false, series_type, &[], page)
+}
 
-	let response = Request::get(&url)?.send()?;
-	let manga = parse_manga(&base_url, response)?;
-	let has_next_page = !manga.is_empty();
+pub fn parse_manga_listing(
+	base_url: String,
+	listing: Listing,
+	page: i32,
+) -> Result<MangaPageResult> {
+	let url = listing_url(&listing.id, page);
 
-	Ok(MangaPageResult {
-		entries: manga,
-		has_next_page,
-	})
+	let response = Request::get(&url)?.send()?;
+	parse_manga(&base_url, response)
 }
 
-pub fn parse_manga_details(base_url: &String, manga_id: String) -> Result<Manga> {
-	let url = format!("{}/series/{}", BASE_API_URL, manga_id);
+pub fn parse_manga_update(
+	base_url: &str,
+	mut manga: Manga,
+	needs_details: bool,
+	needs_chapters: bool,
+) -> Result<Manga> {
+	// the series id is needed for chapters, so the series is fetched either way
+	let url = format!("{}/series/{}", BASE_API_URL, manga.key);
 	let data = Request::get(&url)?.send()?.get_json::<SeriesResponse>()?;
+	let series_id = data.id;
+
+	if needs_details {
+		manga.cover = Some(data.thumbnail);
+		manga.title = data.title;
+		manga.description = Some(data.description);
+		manga.authors = Some(vec![data.author]);
+		manga.artists = Some(vec![data.studio]);
+		manga.key = data.series_slug;
+		manga.url = Some(format!("{}/series/{}", base_url, manga.key));
+		manga.status = match data.status.as_str() {
+			"New" => MangaStatus::Unknown,
+			"Ongoing" => MangaStatus::Ongoing,
+			"Completed" => MangaStatus::Completed,
+			"Cancelled" => MangaStatus::Cancelled,
+			"Dropped" => MangaStatus::Cancelled,
+			"Hiatus" => MangaStatus::Hiatus,
+			_ => MangaStatus::Unknown,
+		};
+		manga.tags = Some(data.tags.into_iter().map(|tag| tag.name).collect());
+		manga.content_rating = ContentRating::NSFW;
+		manga.viewer = if data.series_type == NOVEL_TYPE {
+			Viewer::Vertical
+		} else {
+			Viewer::Webtoon
+		};
+
+		if needs_chapters {
+			send_partial_result(&manga);
+		}
+	}
 
-	let cover = Some(data.thumbnail);
-	let title = data.title;
-	let description = Some(data.description);
-	let authors = vec![data.author];
-	let artists = vec![data.studio];
-	let key = data.series_slug;
-	let url = Some(format!("{}/series/{}", base_url, key));
-	let status_str = data.status;
-
-	let status = match status_str.as_str() {
-		"New" => MangaStatus::Unknown,
-		"Ongoing" => MangaStatus::Ongoing,
-		"Completed" => MangaStatus::Completed,
-		"Cancelled" => MangaStatus::Cancelled,
-		"Dropped" => MangaStatus::Cancelled,
-		"Hiatus" => MangaStatus::Hiatus,
-		_ => MangaStatus::Unknown,
-	};
+	if needs_chapters {
+		manga.chapters = Some(parse_chapter_list(base_url, &manga.key, series_id)?);
+	}
 
-	let tags = data.tags.into_iter().map(|tag| tag.name).collect();
-	let chapters = parse_chapter_list_internal(base_url.clone(), key.clone(), data.id)?;
-
-	Ok(Manga {
-		key,
-		cover,
-		title,
-		authors: Some(authors),
-		artists: Some(artists),
-		description,
-		url,
-		tags: Some(tags),
-		status,
-		content_rating: ContentRating::NSFW,
-		viewer: Viewer::Webtoon,
-		chapters: Some(chapters),
-		..Default::default()
-	})
+	Ok(manga)
 }
 
-fn parse_chapter_list_internal(base_url: String, manga_id: String, series_id: i32) -> Result<Vec<Chapter>> {
-	let url = format!(
-		"{}/chapter/query?page=1&perPage=30&series_id={}",
-		BASE_API_URL, series_id
-	);
-	let data = Request::get(&url)?.send()?.get_json::<ChapterResponse>()?;
-	let mut page = data.meta.first_page;
-	let last_page = data.meta.last_page;
+fn parse_chapter_list(base_url: &str, manga_key: &str, series_id: i32) -> Result<Vec<Chapter>> {
+	let chapter_query_url = |page: i32| {
+		format!(
+			"{}/chapter/query?page={}&perPage={}&series_id={}",
+			BASE_API_URL, page, CHAPTERS_PER_PAGE, series_id
+		)
+	};
 
-	let mut all_chapters: Vec<Chapter> = Vec::new();
+	let first = Request::get(chapter_query_url(1))?
+		.authed()
+		.send()?
+		.get_json::<ChapterResponse>()?;
+	let last_page = first.meta.last_page;
+
+	let mut all_chapters = parse_chapters(base_url, manga_key, first.data);
+
+	// fetch the remaining pages at once, keeping their order
+	if last_page > 1 {
+		let requests = (2..=last_page)
+			.map(|page| Request::get(chapter_query_url(page)).map(|req| req.authed()))
+			.collect::<core::result::Result<Vec<_>, _>>()?;
+		for response in Request::send_all(requests) {
+			let data = response?.get_json::<ChapterResponse>()?;
+			all_chapters.extend(parse_chapters(base_url, manga_key, data.data));
+		}
+	}
 
-	while page <= last_page {
-		let url = format!(
-			"{}/chapter/query?page={}&perPage=30&series_id={}",
-			BASE_API_URL, page, series_id
-		);
-		let data = Request::get(&url)?.send()?.get_json::<ChapterResponse>()?;
+	if auth::is_logged_in() {
+		unlock_purchased_chapters(manga_key, &mut all_chapters);
+	}
 
-		for chapter in data.data {
-			// Only get free chapters
-			if chapter.price != 0 {
-				continue;
-			}
+	if !defaults_get::<bool>(SHOW_LOCKED_KEY).unwrap_or(true) {
+		all_chapters.retain(|chapter| !chapter.locked);
+	}
+
+	Ok(all_chapters)
+}
 
+// the chapter list only has prices, so purchases are checked on each paid chapter,
+// which only has a paywall for accounts that haven't bought it
+fn unlock_purchased_chapters(manga_key: &str, chapters: &mut [Chapter]) {
+	let paid = chapters
+		.iter_mut()
+		.filter(|chapter| chapter.locked)
+		.collect::<Vec<_>>();
+	if paid.is_empty() {
+		return;
+	}
+	let Ok(requests) = paid
+		.iter()
+		.map(|chapter| {
+			Request::get(chapter_details_url(manga_key, &chapter.key)).map(|req| req.authed())
+		})
+		.collect::<core::result::Result<Vec<_>, _>>()
+	else {
+		return;
+	};
+	for (chapter, response) in paid.into_iter().zip(Request::send_all(requests)) {
+		let purchased = response
+			.ok()
+			.and_then(|mut response| response.get_json::<ChapterDetailsResponse>().ok())
+			.is_some_and(|details| !details.paywall);
+		if purchased {
+			chapter.locked = false;
+		}
+	}
+}
+
+// paid chapters are locked until purchased
+fn parse_chapters(base_url: &str, manga_key: &str, chapters: Vec<ChapterData>) -> Vec<Chapter> {
+	chapters
+		.into_iter()
+		.map(|chapter| {
+			let locked = chapter.price != 0;
 			let key = chapter.chapter_slug;
 
 			let index = key.split('-').collect::<Vec<&str>>();
@@ -225,36 +391,120 @@ fn parse_chapter_list_internal(base_url: String, manga_id: String, series_id: i3
 				None
 			};
 
-			let url = Some(format!("{}/series/{}/{}", base_url, manga_id, key));
+			let url = Some(format!("{}/series/{}/{}", base_url, manga_key, key));
 
 			// Parse the date - this might fail, that's ok
 			let date_uploaded = chrono::DateTime::parse_from_rfc3339(&chapter.created_at)
 				.map(|dt| dt.timestamp())
 				.ok();
 
-			all_chapters.push(Chapter {
+			Chapter {
 				key,
-				title: None,
 				chapter_number,
-				volume_number: None,
 				date_uploaded,
 				url,
+				locked,
 				..Default::default()
-			});
+			}
+		})
+		.collect()
+}
+
+pub fn parse_page_list(
+	base_url: String,
+	manga_key: String,
+	chapter_key: String,
+) -> Result<Vec<Page>> {
+	if let Ok(mut details) = get_chapter_details(&manga_key, &chapter_key) {
+		let paywall = details.paywall;
+		if let Some(content) = details
+			.chapter
+			.chapter_content
+			.take()
+			.filter(|content| !content.trim().is_empty())
+		{
+			let text = Html::parse_fragment(&content)?
+				.select_first("body")
+				.map(|body| html_to_text(&body))
+				.unwrap_or_default();
+			if !text.is_empty() {
+				return Ok(vec![Page {
+					content: PageContent::text(text),
+					..Default::default()
+				}]);
+			}
+		}
+		let images = chapter_image_urls(details);
+		if !images.is_empty() {
+			return Ok(images
+				.into_iter()
+				.map(|url| Page {
+					content: PageContent::url(url),
+					..Default::default()
+				})
+				.collect());
+		}
+		// the html won't have the pages of a paywalled chapter either
+		if paywall {
+			bail!("This chapter needs to be purchased to be read");
 		}
-		page += 1;
 	}
+	parse_page_list_html(base_url, manga_key, chapter_key)
+}
 
-	Ok(all_chapters)
+fn chapter_details_url(manga_key: &str, chapter_key: &str) -> String {
+	format!("{}/chapter/{}/{}", BASE_API_URL, manga_key, chapter_key)
 }
 
-pub fn parse_page_list(
+fn get_chapter_details(manga_key: &str, chapter_key: &str) -> Result<ChapterDetailsResponse> {
+	let url = chapter_details_url(manga_key, chapter_key);
+	Ok(Request::get(&url)?
+		.authed()
+		.send()?
+		.get_json::<ChapterDetailsResponse>()?)
+}
+
+// `images` is a plain array of urls without page numbers, and the site's reader shows them
+// in array order, so that order is the page order and is kept as is
+fn chapter_image_urls(details: ChapterDetailsResponse) -> Vec<String> {
+	details
+		.chapter
+		.chapter_data
+		.map(|chapter_data| chapter_data.images)
+		.unwrap_or_default()
+		.into_iter()
+		.filter(|image| !image.is_empty())
+		.map(|image| {
+			if image.starts_with("http") {
+				image
+			} else {
+				format!("{}/{}", BASE_API_URL, image.trim_start_matches('/'))
+			}
+		})
+		.collect()
+}
+
+fn parse_page_list_html(
 	base_url: String,
 	manga_key: String,
 	chapter_key: String,
 ) -> Result<Vec<Page>> {
 	let url = format!("{}/series/{}/{}", base_url, manga_key, chapter_key);
-	let obj = Request::get(&url)?.html()?;
+	let obj = Request::get(&url)?.authed().html()?;
+
+	// novel chapters are rendered as text in the reader container
+	if let Some(reader) = obj
+		.select_first("#reader-container")
+		.filter(|el| el.select_first("p").is_some())
+	{
+		let text = html_to_text(&reader);
+		if !text.is_empty() {
+			return Ok(vec![Page {
+				content: PageContent::text(text),
+				..Default::default()
+			}]);
+		}
+	}
 
 	let mut page_list: Vec<Page> = Vec::new();
 
@@ -299,8 +549,38 @@ pub fn parse_page_list(
 	Ok(page_list)
 }
 
-fn parse_manga(base_url: &String, mut response: aidoku::imports::net::Response) -> Result<Vec<Manga>> {
+// joins the paragraphs of novel chapter html into plain text
+fn html_to_text(html: &Element) -> String {
+	html.select("p")
+		.map(|els| {
+			els.filter_map(|el| el.text())
+				.map(|text| String::from(text.trim()))
+				.filter(|text| !text.is_empty())
+				.collect::<Vec<_>>()
+				.join("\n\n")
+		})
+		.filter(|text| !text.is_empty())
+		.or_else(|| html.text())
+		.unwrap_or_default()
+}
+
+fn has_next_page(meta: Option<&MetaData>, count: usize) -> bool {
+	match meta {
+		Some(MetaData {
+			current_page: Some(current_page),
+			last_page,
+		}) => current_page < last_page,
+		// without pagination info, only a full page can have more results
+		_ => count as i32 >= MANGA_PER_PAGE,
+	}
+}
+
+pub fn parse_manga(
+	base_url: &str,
+	mut response: aidoku::imports::net::Response,
+) -> Result<MangaPageResult> {
 	let data = response.get_json::<ApiResponse>()?;
+	let has_next_page = has_next_page(data.meta.as_ref(), data.data.len());
 	let mut mangas: Vec<Manga> = Vec::new();
 
 	for manga in data.data {
@@ -315,5 +595,134 @@ fn parse_manga(base_url: &String, mut response: aidoku::imports::net::Response)
 		});
 	}
 
-	Ok(mangas)
+	Ok(MangaPageResult {
+		entries: mangas,
+		has_next_page,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use aidoku_test::aidoku_test;
+
+	#[aidoku_test]
+	fn chapter_images_keep_api_order() {
+		let details = ChapterDetailsResponse {
+			chapter: ChapterDetails {
+				chapter_data: Some(ChapterImageData {
+					images: vec![
+						"uploads/series/test/10.jpg".into(),
+						String::new(),
+						"https://media.omegascans.org/file/2.jpg".into(),
+						"/uploads/series/test/1.jpg".into(),
+					],
+				}),
+				chapter_content: None,
+			},
+			paywall: false,
+		};
+		assert_eq!(
+			chapter_image_urls(details),
+			[
+				format!("{}/uploads/series/test/10.jpg", BASE_API_URL),
+				String::from("https://media.omegascans.org/file/2.jpg"),
+				format!("{}/uploads/series/test/1.jpg", BASE_API_URL),
+			]
+		);
+	}
+
+	#[aidoku_test]
+	fn chapter_list_payload() {
+		let json = r#"{
+			"meta": {"total": 62, "per_page": 30, "current_page": 1, "last_page": 3},
+			"data": [
+				{
+					"id": 94210,
+					"chapter_name": "Chapter 62",
+					"chapter_title": null,
+					"chapter_thumbnail": "https://media.omegascans.org/file/thumb.webp",
+					"chapter_slug": "chapter-62",
+					"price": 75,
+					"created_at": "2024-06-14T16:00:00.000Z",
+					"series": {"series_slug": "test-series", "id": 412}
+				},
+				{
+					"id": 93871,
+					"chapter_name": "Chapter 61",
+					"chapter_title": null,
+					"chapter_thumbnail": "https://media.omegascans.org/file/thumb.webp",
+					"chapter_slug": "chapter-61",
+					"price": 0,
+					"created_at": "2024-06-07T16:00:00.000Z",
+					"series": {"series_slug": "test-series", "id": 412}
+				}
+			]
+		}"#;
+		let response = serde_json::from_str::<ChapterResponse>(json).unwrap();
+		assert_eq!(response.meta.last_page, 3);
+		let chapters = parse_chapters("https://omegascans.org", "test-series", response.data);
+		assert_eq!(chapters.len(), 2);
+		assert_eq!(chapters[0].key, "chapter-62");
+		assert_eq!(chapters[0].chapter_number, Some(62.0));
+		assert!(chapters[0].locked);
+		assert!(!chapters[1].locked);
+		assert_eq!(
+			chapters[1].url.as_deref(),
+			Some("https://omegascans.org/series/test-series/chapter-61")
+		);
+	}
+
+	#[aidoku_test]
+	fn chapter_details_paywall_payload() {
+		let json = r#"{
+			"chapter": {
+				"id": 94210,
+				"chapter_name": "Chapter 62",
+				"chapter_slug": "chapter-62",
+				"price": 75,
+				"chapter_data": null,
+				"chapter_content": null
+			},
+			"paywall": true
+		}"#;
+		let details = serde_json::from_str::<ChapterDetailsResponse>(json).unwrap();
+		assert!(details.paywall);
+		assert!(chapter_image_urls(details).is_empty());
+
+		let json = r#"{
+			"chapter": {
+				"id": 93871,
+				"chapter_slug": "chapter-61",
+				"price": 0,
+				"chapter_data": {"images": ["https://media.omegascans.org/file/01.jpg"]}
+			}
+		}"#;
+		let details = serde_json::from_str::<ChapterDetailsResponse>(json).unwrap();
+		assert!(!details.paywall);
+		assert_eq!(chapter_image_urls(details).len(), 1);
+	}
+
+	#[aidoku_test]
+	fn has_next_page_uses_meta() {
+		let meta = |current_page, last_page| MetaData {
+			current_page,
+			last_page,
+		};
+		assert!(has_next_page(Some(&meta(Some(1), 3)), 10));
+		assert!(!has_next_page(Some(&meta(Some(3), 3)), 10));
+		assert!(!has_next_page(Some(&meta(Some(1), 1)), 10));
+	}
+
+	#[aidoku_test]
+	fn has_next_page_without_current_page() {
+		let meta = MetaData {
+			current_page: None,
+			last_page: 5,
+		};
+		assert!(has_next_page(Some(&meta), MANGA_PER_PAGE as usize));
+		assert!(!has_next_page(Some(&meta), 3));
+		assert!(has_next_page(None, MANGA_PER_PAGE as usize));
+		assert!(!has_next_page(None, 0));
+	}
 }
@@ -0,0 +1,124 @@
+use crate::BASE_URL;
+use aidoku::{
+	alloc::{String, Vec},
+	helpers::uri::encode_uri_component,
+	imports::{
+		defaults::{defaults_get, defaults_set, DefaultValue},
+		net::Request,
+	},
+	prelude::*,
+	Result,
+};
+
+static EMAIL_KEY: &str = "login.username";
+static COOKIE_KEY: &str = "login.cookie";
+
+// markers that are only shown to logged in users
+const LOGGED_IN_SELECTORS: &str =
+	"a[href*=/auth/logout], form[action*=/auth/logout], [hx-post*=/auth/logout]";
+
+pub fn login(email: &str, password: &str) -> Result<bool> {
+	let url = format!("{BASE_URL}/auth/login");
+	let body = format!(
+		"email={}&password={}&remember_me=on",
+		encode_uri_component(email),
+		encode_uri_component(password)
+	);
+	let res = Request::post(&url)?
+		.header("Content-Type", "application/x-www-form-urlencoded")
+		.header("HX-Request", "true")
+		.body(&body)
+		.send()?;
+
+	// a failed login can still set a session cookie, so the status and cookie alone aren't enough
+	let Some(cookie) = res
+		.get_header("Set-Cookie")
+		.map(|header| cookie_pairs(&header))
+		.filter(|cookie| !cookie.is_empty())
+	else {
+		return Ok(false);
+	};
+	if !(200..400).contains(&res.status_code()) {
+		return Ok(false);
+	}
+
+	// confirm the session by loading a page that differs for logged in users
+	let logged_in = Request::get(BASE_URL)?
+		.header("Cookie", &cookie)
+		.html()
+		.is_ok_and(|html| html.select_first(LOGGED_IN_SELECTORS).is_some());
+	if logged_in {
+		defaults_set(COOKIE_KEY, DefaultValue::String(cookie));
+	}
+	Ok(logged_in)
+}
+
+// keeps only the name=value pairs from a (possibly combined) set-cookie header
+fn cookie_pairs(header: &str) -> String {
+	let mut pairs: Vec<&str> = Vec::new();
+	for part in header.split(',') {
+		// attributes follow the first semicolon, and expiry dates contain commas
+		let pair = part.split(';').next().unwrap_or_default().trim();
+		let Some((name, _)) = pair.split_once('=') else {
+			continue;
+		};
+		if name.is_empty() || name.contains(' ') {
+			continue;
+		}
+		const ATTRIBUTES: [&str; 6] = [
+			"expires", "max-age", "domain", "path", "samesite", "priority",
+		];
+		if ATTRIBUTES
+			.iter()
+			.any(|attr| name.eq_ignore_ascii_case(attr))
+		{
+			continue;
+		}
+		pairs.push(pair);
+	}
+	pairs.join("; ")
+}
+
+pub fn logout() {
+	defaults_set(COOKIE_KEY, DefaultValue::Null);
+}
+
+pub fn is_logged_in() -> bool {
+	defaults_get::<String>(EMAIL_KEY).is_some() && defaults_get::<String>(COOKIE_KEY).is_some()
+}
+
+pub trait AuthedRequest {
+	fn authed(self) -> Self;
+}
+
+impl AuthedRequest for Request {
+	fn authed(self) -> Self {
+		let cookie = defaults_get::<String>(COOKIE_KEY);
+		if let Some(cookie) = cookie {
+			self.header("Cookie", &cookie)
+		} else {
+			self
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use aidoku_test::aidoku_test;
+
+	#[aidoku_test]
+	fn cookie_pairs_strip_attributes() {
+		assert_eq!(
+			cookie_pairs("session=abc123; Path=/; HttpOnly; SameSite=Lax"),
+			"session=abc123"
+		);
+		assert_eq!(
+			cookie_pairs(
+				"session=abc; Expires=Wed, 21 Oct 2026 07:28:00 GMT; Path=/, remember=xyz; Max-Age=3600; Secure"
+			),
+			"session=abc; remember=xyz"
+		);
+		assert_eq!(cookie_pairs("Path=/; HttpOnly"), "");
+	}
+}
@@ -1,5 +1,5 @@
 use crate::helper;
-use crate::model::SortOptions;
+use crate::model::{IncludeExclude, SearchFilters, SortOptions, TriState};
 use aidoku::{
 	alloc::{borrow::ToOwned, String, Vec},
 	helpers::uri::QueryParameters,
@@ -7,64 +7,92 @@ use aidoku::{
 	FilterValue,
 };
 
-pub fn get_filters(query: Option<String>, filters: Vec<FilterValue>) -> String {
-	let mut qs = QueryParameters::new();
-
-	if let Some(query) = query {
-		let cleaned = helper::remove_special_chars(query).trim().to_owned();
-		if !cleaned.is_empty() {
-			qs.push("text", Some(&cleaned));
-		}
-	}
-
-	let mut extra_params = String::new();
+fn parse_filters(query: Option<String>, filters: Vec<FilterValue>) -> SearchFilters {
+	let mut search = SearchFilters {
+		text: query
+			.map(|query| helper::remove_special_chars(query).trim().to_owned())
+			.filter(|query| !query.is_empty()),
+		..Default::default()
+	};
 
 	for filter in filters {
 		match filter {
-			FilterValue::Text { ref value, .. } => {
-				if !value.is_empty() {
-					qs.push("author", Some(value));
+			FilterValue::Text { id, value } => {
+				if id == "author" && !value.is_empty() {
+					search.author = Some(value);
 				}
 			}
 			FilterValue::Sort {
 				index, ascending, ..
 			} => {
-				let option: &str = SortOptions::from(index).into();
-				qs.push("sort", Some(option));
-				qs.push(
-					"order",
-					Some(if ascending { "Ascending" } else { "Descending" }),
-				);
+				search.sort = Some((SortOptions::from(index), ascending));
 			}
 			FilterValue::MultiSelect {
-				ref id,
-				ref included,
-				ref excluded,
-			} => {
-				if id == "genre" {
-					for tag in included {
-						qs.push("included_tag", Some(tag));
-					}
-					for tag in excluded {
-						qs.push("excluded_tag", Some(tag));
-					}
-				} else {
-					for val in included {
-						extra_params.push_str(val);
-					}
-				}
-			}
-			FilterValue::Check { value, .. } => qs.push(
-				"official",
-				Some(match value {
-					0 => "False",
-					1 => "True",
-					_ => "Any",
-				}),
-			),
+				id,
+				included,
+				excluded,
+			} => match id.as_str() {
+				"status" => search.status = IncludeExclude::from_values(&included, &excluded),
+				"type" => search.series_type = IncludeExclude::from_values(&included, &excluded),
+				"genre" => search.tags = IncludeExclude::from_values(&included, &excluded),
+				_ => {}
+			},
+			FilterValue::Check { id, value } => match id.as_str() {
+				"official" => search.official = TriState::from(value),
+				"adult" => search.adult = TriState::from(value),
+				_ => {}
+			},
 			_ => {}
 		}
 	}
 
-	format!("{qs}{extra_params}")
+	search
+}
+
+pub fn get_filters(query: Option<String>, filters: Vec<FilterValue>) -> String {
+	let search = parse_filters(query, filters);
+	let mut qs = QueryParameters::new();
+
+	if let Some(text) = &search.text {
+		qs.push("text", Some(text));
+	}
+	if let Some(author) = &search.author {
+		qs.push("author", Some(author));
+	}
+	if let Some((sort, ascending)) = search.sort {
+		let option: &str = sort.into();
+		qs.push("sort", Some(option));
+		qs.push(
+			"order",
+			Some(if ascending { "Ascending" } else { "Descending" }),
+		);
+	}
+	for status in &search.status.included {
+		let status: &str = status.into();
+		qs.push("included_status", Some(status));
+	}
+	for status in &search.status.excluded {
+		let status: &str = status.into();
+		qs.push("excluded_status", Some(status));
+	}
+	for series_type in &search.series_type.included {
+		let series_type: &str = series_type.into();
+		qs.push("included_type", Some(series_type));
+	}
+	for series_type in &search.series_type.excluded {
+		let series_type: &str = series_type.into();
+		qs.push("excluded_type", Some(series_type));
+	}
+	for tag in &search.tags.included {
+		qs.push("included_tag", Some(tag));
+	}
+	for tag in &search.tags.excluded {
+		qs.push("excluded_tag", Some(tag));
+	}
+	let official: &str = (&search.official).into();
+	qs.push("official", Some(official));
+	let adult: &str = (&search.adult).into();
+	qs.push("adult", Some(adult));
+
+	format!("{qs}")
 }
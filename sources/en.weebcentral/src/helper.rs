@@ -1,4 +1,12 @@
-use aidoku::alloc::String;
+use aidoku::{
+	alloc::String,
+	imports::{
+		html::Document,
+		net::{Request, Response},
+	},
+	prelude::*,
+	AidokuError, Result,
+};
 
 pub fn remove_special_chars(string: String) -> String {
 	let chars_to_replace = ['[', '!', '#', ':', '(', ')', ']'];
@@ -14,3 +22,71 @@ pub fn remove_special_chars(string: String) -> String {
 
 	result
 }
+
+// markers found on cloudflare's challenge pages
+const CHALLENGE_SELECTORS: &str =
+	"#challenge-form, #challenge-running, #cf-challenge-running, .cf-browser-verification";
+const CHALLENGE_TITLES: [&str; 3] = ["Just a moment...", "Attention Required!", "Access denied"];
+
+// sends the request and parses the response as html, returning a descriptive error
+// instead of the page when the site serves a challenge, rate limit or maintenance page
+pub trait CheckedRequest {
+	fn checked_html(self) -> Result<Document>;
+}
+
+impl CheckedRequest for Request {
+	fn checked_html(self) -> Result<Document> {
+		let response = self.send()?;
+		check_response(response)
+	}
+}
+
+fn check_response(response: Response) -> Result<Document> {
+	let status_code = response.status_code();
+	// cloudflare sets this header when it serves a challenge instead of the page
+	let is_mitigated = response
+		.get_header("cf-mitigated")
+		.is_some_and(|value| value.eq_ignore_ascii_case("challenge"));
+
+	if status_code == 429 {
+		let retry_after = response
+			.get_header("Retry-After")
+			.map(|seconds| format!(" Try again in {seconds} seconds."))
+			.unwrap_or_default();
+		return Err(AidokuError::message(&format!(
+			"Weeb Central is rate limiting requests.{retry_after}"
+		)));
+	}
+
+	let html = response.get_html()?;
+
+	let title = html
+		.select_first("title")
+		.and_then(|el| el.text())
+		.unwrap_or_default();
+	// page titles include series names, so they're only checked on error responses
+	let is_error = status_code >= 400;
+	let is_challenge = html.select_first(CHALLENGE_SELECTORS).is_some()
+		|| (is_error && CHALLENGE_TITLES.iter().any(|t| title.contains(t)));
+	if is_mitigated || is_challenge {
+		return Err(AidokuError::message(
+			"Cloudflare verification required. Open Weeb Central in the web view to continue.",
+		));
+	}
+
+	if matches!(status_code, 502..=504)
+		|| (status_code >= 500 && title.to_lowercase().contains("maintenance"))
+	{
+		return Err(AidokuError::message(
+			"Weeb Central is currently down for maintenance. Try again later.",
+		));
+	}
+
+	if is_error {
+		return Err(AidokuError::message(&format!(
+			"Weeb Central returned an error (HTTP {status_code})"
+		)));
+	}
+
+	Ok(html)
+}
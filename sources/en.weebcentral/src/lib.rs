@@ -3,18 +3,26 @@ use aidoku::{
 	alloc::{borrow::ToOwned, vec, String, Vec},
 	imports::{html::Element, net::Request, std::send_partial_result},
 	prelude::*,
-	AidokuError, Chapter, ContentRating, DeepLinkHandler, DeepLinkResult, FilterValue, Home,
-	HomeComponent, HomeLayout, ImageRequestProvider, Listing, ListingProvider, Manga,
-	MangaPageResult, MangaStatus, MangaWithChapter, Page, PageContent, Result, Source, Viewer,
+	AidokuError, AlternateCoverProvider, BasicLoginHandler, Chapter, ContentRating,
+	DeepLinkHandler, DeepLinkResult, DynamicListings, FilterValue, Home, HomeComponent, HomeLayout,
+	ImageRequestProvider, Listing, ListingKind, ListingProvider, Manga, MangaPageResult,
+	MangaStatus, NotificationHandler, Page, PageContent, Result, Source, Viewer,
 };
 
+mod auth;
 mod filter;
 mod helper;
 mod model;
+mod parser;
+mod settings;
+
+use auth::AuthedRequest;
+use helper::CheckedRequest;
 
 const BASE_URL: &str = "https://weebcentral.com";
 const REFERER: &str = "https://weebcentral.com/";
-const FETCH_LIMIT: i32 = 24;
+const SUBSCRIPTIONS_LISTING_ID: &str = "subscriptions";
+const HISTORY_LISTING_ID: &str = "history";
 
 struct WeebCentral;
 
@@ -29,18 +37,19 @@ impl Source for WeebCentral {
 		page: i32,
 		filters: Vec<FilterValue>,
 	) -> Result<MangaPageResult> {
-		let offset = (page - 1) * FETCH_LIMIT;
+		let fetch_limit = settings::get_fetch_limit();
+		let offset = (page - 1) * fetch_limit;
 
 		let url = format!(
 			"{BASE_URL}/search/data\
-					?limit={FETCH_LIMIT}\
+					?limit={fetch_limit}\
 					&offset={offset}\
 					&display_mode=Full+Display\
 					&{}",
 			filter::get_filters(query, filters)
 		);
 
-		let html = Request::get(&url)?.html()?;
+		let html = Request::get(&url)?.checked_html()?;
 
 		let entries = html
 			.select("article:has(section)")
@@ -87,9 +96,22 @@ impl Source for WeebCentral {
 	) -> Result<Manga> {
 		let manga_url = format!("{BASE_URL}{}", manga.key);
 
-		if needs_details {
-			let html = Request::get(&manga_url)?.html()?;
+		// refreshes with known chapters can use the recent chapters on the series page instead of the full list
+		let known_chapters = if needs_chapters {
+			manga
+				.chapters
+				.take()
+				.filter(|chapters| !chapters.is_empty())
+		} else {
+			None
+		};
+		let series_html = if needs_details || (needs_chapters && known_chapters.is_some()) {
+			Some(Request::get(&manga_url)?.checked_html()?)
+		} else {
+			None
+		};
 
+		if let (true, Some(html)) = (needs_details, series_html.as_ref()) {
 			let elements = html.select("section[x-data] > section");
 			let (info_element, title_element) = match elements.as_ref() {
 				Some(els) if !els.is_empty() => {
@@ -112,13 +134,64 @@ impl Source for WeebCentral {
 			manga.authors = info_element
 				.select("ul > li:has(strong:contains(Author)) > span > a")
 				.map(|els| els.filter_map(|el| el.text()).collect::<Vec<String>>());
-			manga.description =
-				get_text(&title_element, "li:has(strong:contains(Description)) > p");
 			manga.url = Some(manga_url.clone());
 			manga.tags = info_element
 				.select("ul > li:has(strong:contains(Tag),strong:contains(Type)) a")
 				.map(|els| els.filter_map(|el| el.text()).collect::<Vec<String>>());
 
+			// the release year and official translation flag have no dedicated fields, so they're added as tags
+			let year = get_text(
+				&info_element,
+				"ul > li:has(strong:contains(Released)) > span",
+			)
+			.filter(|year| !year.is_empty());
+			let is_official = get_text(
+				&info_element,
+				"ul > li:has(strong:contains(Official Translation)) a",
+			)
+			.is_some_and(|official| official == "Yes");
+			if year.is_some() || is_official {
+				let tags = manga.tags.get_or_insert_with(Vec::new);
+				tags.extend(year);
+				if is_official {
+					tags.push("Official Translation".into());
+				}
+			}
+
+			// associated names and related series are listed after the description
+			let list_texts = |sel: &str| {
+				html.select(sel)
+					.map(|els| els.filter_map(|el| el.text()).collect::<Vec<String>>())
+					.unwrap_or_default()
+			};
+			let alt_titles = list_texts("li:has(strong:contains(Associated Name)) li");
+			// related series link to their series pages, which open through the deep link handler
+			let related_series = parser::parse_related_series(html)
+				.into_iter()
+				.map(|manga| format!("{} ({BASE_URL}{})", manga.title, manga.key))
+				.collect::<Vec<_>>();
+			let mut description =
+				get_text(&title_element, "li:has(strong:contains(Description)) > p")
+					.unwrap_or_default();
+			for (heading, items) in [
+				("Associated Names", alt_titles),
+				("Related Series", related_series),
+			] {
+				if items.is_empty() {
+					continue;
+				}
+				if !description.is_empty() {
+					description.push_str("\n\n");
+				}
+				description.push_str(heading);
+				description.push(':');
+				for item in items {
+					description.push_str("\n• ");
+					description.push_str(&item);
+				}
+			}
+			manga.description = (!description.is_empty()).then_some(description);
+
 			let status_str = info_element
 				.select_first("ul > li:has(strong:contains(Status)) a")
 				.and_then(|x| x.text())
@@ -157,58 +230,35 @@ impl Source for WeebCentral {
 		}
 
 		if needs_chapters {
-			let url = manga_url
-				.rfind('/')
-				.map(|pos| format!("{}/full-chapter-list", &manga_url[..pos]))
-				.unwrap_or_else(|| manga_url.clone());
-
-			let html = Request::get(&url)?.html()?;
-
-			manga.chapters = html.select("div[x-data]").map(|elements| {
-				elements
-					.filter_map(|element| {
-						let url = element
-							.select_first("a")
-							.and_then(|el| el.attr("abs:href"))?;
-
-						let key = url.strip_prefix(BASE_URL)?.into();
-
-						let title = element
-							.select_first("span.flex > span")
-							.and_then(|el| el.text());
-
-						let mut chapter_number = title
-							.as_ref()
-							.and_then(|t| t.rsplit(' ').next())
-							.and_then(|num| num.parse::<f32>().ok());
-
-						let is_volume = title.as_ref().is_some_and(|t| t.contains("Volume"));
-						let is_chapter = title.as_ref().is_some_and(|t| t.contains("Chapter"));
-
-						let (final_title, volume_number) = match (is_volume, is_chapter) {
-							(true, _) => (None, chapter_number.take()),
-							(_, true) => (None, None),
-							_ => (title, None),
-						};
-
-						let date_uploaded = element
-							.select_first("time[datetime]")
-							.and_then(|el| el.attr("datetime"))
-							.and_then(|dt| chrono::DateTime::parse_from_rfc3339(&dt).ok())
-							.map(|d| d.timestamp());
+			let series_chapters = series_html.as_ref().map(parser::parse_series_chapters);
+			let (recent_chapters, full_list_url, is_complete) = match series_chapters {
+				Some(parser::SeriesChapters {
+					chapters,
+					full_list_url,
+					is_complete,
+				}) => (chapters, full_list_url, is_complete),
+				None => (Vec::new(), None, false),
+			};
 
-						Some(Chapter {
-							key,
-							title: final_title,
-							chapter_number,
-							volume_number,
-							date_uploaded,
-							url: Some(url),
-							..Default::default()
-						})
-					})
-					.collect::<Vec<_>>()
-			});
+			let chapters = if is_complete {
+				// the series page already lists every chapter
+				Some(recent_chapters)
+			} else if let Some(chapters) =
+				known_chapters.and_then(|known| parser::merge_chapters(recent_chapters, known))
+			{
+				// the recent chapters overlap the known ones, so nothing in between was missed
+				Some(chapters)
+			} else {
+				let url = full_list_url.unwrap_or_else(|| {
+					manga_url
+						.rfind('/')
+						.map(|pos| format!("{}/full-chapter-list", &manga_url[..pos]))
+						.unwrap_or_else(|| manga_url.clone())
+				});
+				let html = Request::get(&url)?.checked_html()?;
+				Some(parser::parse_full_chapter_list(&html))
+			};
+			manga.chapters = chapters;
 		}
 
 		Ok(manga)
@@ -219,7 +269,7 @@ impl Source for WeebCentral {
 			"{BASE_URL}{}/images?is_prev=False&reading_style=long_strip",
 			chapter.key
 		);
-		let html = Request::get(url)?.html()?;
+		let html = Request::get(url)?.checked_html()?;
 
 		let pages = html
 			.select("section[x-data*=scroll] > img")
@@ -240,9 +290,16 @@ impl Source for WeebCentral {
 }
 
 impl ListingProvider for WeebCentral {
-	fn get_manga_list(&self, listing: Listing, _page: i32) -> Result<MangaPageResult> {
+	fn get_manga_list(&self, listing: Listing, page: i32) -> Result<MangaPageResult> {
 		if listing.id == "hot" {
-			let html = Request::get(format!("{BASE_URL}/hot-updates"))?.html()?;
+			// hot updates are shown on a single page
+			if page > 1 {
+				return Ok(MangaPageResult {
+					entries: Vec::new(),
+					has_next_page: false,
+				});
+			}
+			let html = Request::get(format!("{BASE_URL}/hot-updates"))?.checked_html()?;
 
 			let entries = html
 				.select("article:not(.hidden)")
@@ -266,6 +323,94 @@ impl ListingProvider for WeebCentral {
 				})
 				.unwrap_or_default();
 
+			Ok(MangaPageResult {
+				entries,
+				has_next_page: false,
+			})
+		} else if listing.id == "latest" {
+			let html = Request::get(format!("{BASE_URL}/latest-updates/{page}"))?.checked_html()?;
+
+			let entries = html
+				.select("article")
+				.map(|els| {
+					els.filter_map(|el| parser::parse_manga_card(&el))
+						.collect::<Vec<_>>()
+				})
+				.unwrap_or_default();
+
+			// the next page is loaded by a "view more" button
+			let has_next_page = html
+				.select_first(format!("[hx-get*=\"/latest-updates/{}\"]", page + 1))
+				.is_some();
+
+			Ok(MangaPageResult {
+				entries,
+				has_next_page,
+			})
+		} else if listing.id == "recent" {
+			self.get_search_manga_list(
+				None,
+				page,
+				vec![FilterValue::Sort {
+					id: "sort".into(),
+					index: 4, // recently added
+					ascending: false,
+				}],
+			)
+		} else if listing.id == "official" {
+			self.get_search_manga_list(
+				None,
+				page,
+				vec![
+					FilterValue::Sort {
+						id: "sort".into(),
+						index: 2, // popularity
+						ascending: false,
+					},
+					FilterValue::Check {
+						id: "official".into(),
+						value: 1,
+					},
+				],
+			)
+		} else if listing.id == SUBSCRIPTIONS_LISTING_ID {
+			if !auth::is_logged_in() {
+				bail!("Log in to view your subscriptions");
+			}
+			let html = Request::get(format!("{BASE_URL}/subscriptions"))?
+				.authed()
+				.checked_html()?;
+
+			let entries = html
+				.select("article")
+				.map(|els| {
+					els.filter_map(|el| parser::parse_manga_card(&el))
+						.collect::<Vec<_>>()
+				})
+				.unwrap_or_default();
+
+			Ok(MangaPageResult {
+				entries,
+				has_next_page: false,
+			})
+		} else if listing.id == HISTORY_LISTING_ID {
+			if !auth::is_logged_in() {
+				bail!("Log in to view your reading history");
+			}
+			// the continue reading section is only shown on the home page
+			if page > 1 {
+				return Ok(MangaPageResult {
+					entries: Vec::new(),
+					has_next_page: false,
+				});
+			}
+			let html = Request::get(BASE_URL)?.authed().checked_html()?;
+
+			let entries = parser::parse_continue_reading(&html)
+				.into_iter()
+				.map(|item| item.manga)
+				.collect::<Vec<_>>();
+
 			Ok(MangaPageResult {
 				entries,
 				has_next_page: false,
@@ -278,41 +423,8 @@ impl ListingProvider for WeebCentral {
 
 impl Home for WeebCentral {
 	fn get_home(&self) -> Result<HomeLayout> {
-		let html = Request::get(BASE_URL)?.html()?;
-
-		fn parse_manga_with_chapter(el: &Element) -> Option<MangaWithChapter> {
-			let mut links = el.select("a")?;
-			let manga_key = links.first()?.attr("href")?.strip_prefix(BASE_URL)?.into();
-			let chapter_link = links.next_back()?;
-			let chapter_key = chapter_link.attr("href")?.strip_prefix(BASE_URL)?.into();
-			let cover = el.select_first("img")?.attr("src");
-			let title = el.select_first(".text-lg")?.text()?;
-			let chapter_number = chapter_link
-				.select_first("div.flex")?
-				.text()
-				.as_ref()
-				.and_then(|t| t.rsplit(' ').next())
-				.and_then(|num| num.parse::<f32>().ok());
-			let date_uploaded = el
-				.select_first("time[datetime]")
-				.and_then(|el| el.attr("datetime"))
-				.and_then(|dt| chrono::DateTime::parse_from_rfc3339(&dt).ok())
-				.map(|d| d.timestamp());
-			Some(MangaWithChapter {
-				manga: Manga {
-					key: manga_key,
-					title,
-					cover,
-					..Default::default()
-				},
-				chapter: Chapter {
-					key: chapter_key,
-					chapter_number,
-					date_uploaded,
-					..Default::default()
-				},
-			})
-		}
+		// logged in users also get their continue reading section
+		let html = Request::get(BASE_URL)?.authed().checked_html()?;
 
 		fn parse_manga(el: &Element) -> Option<Manga> {
 			let key = el
@@ -330,10 +442,12 @@ impl Home for WeebCentral {
 			})
 		}
 
+		let continue_reading = parser::parse_continue_reading(&html);
+
 		let hot_updates = html
 			.select("section:has(h2:contains(Hot Updates)) article:not(.hidden)")
 			.map(|els| {
-				els.filter_map(|el| parse_manga_with_chapter(&el))
+				els.filter_map(|el| parser::parse_manga_with_chapter(&el))
 					.collect::<Vec<_>>()
 			})
 			.unwrap_or_default();
@@ -341,7 +455,7 @@ impl Home for WeebCentral {
 		let latest_updates = html
 			.select("section:has(h2:contains(Latest Updates)) article")
 			.map(|els| {
-				els.filter_map(|el| parse_manga_with_chapter(&el))
+				els.filter_map(|el| parser::parse_manga_with_chapter(&el))
 					.collect::<Vec<_>>()
 			})
 			.unwrap_or_default();
@@ -351,40 +465,134 @@ impl Home for WeebCentral {
 			.map(|els| els.filter_map(|el| parse_manga(&el).map(Into::into)).collect::<Vec<_>>())
 			.unwrap_or_default();
 
-		Ok(HomeLayout {
-			components: vec![
-				HomeComponent {
-					title: Some("Hot Updates".into()),
-					subtitle: None,
-					value: aidoku::HomeComponentValue::MangaChapterList {
-						page_size: Some(6),
-						entries: hot_updates,
-						listing: Some(Listing {
-							id: "hot".into(),
-							name: "Hot Updates".into(),
-							..Default::default()
-						}),
-					},
+		let mut components = Vec::new();
+		if !continue_reading.is_empty() {
+			components.push(HomeComponent {
+				title: Some("Continue Reading".into()),
+				subtitle: None,
+				value: aidoku::HomeComponentValue::MangaChapterList {
+					page_size: Some(4),
+					entries: continue_reading,
+					listing: Some(Listing {
+						id: HISTORY_LISTING_ID.into(),
+						name: "Continue Reading".into(),
+						..Default::default()
+					}),
 				},
-				HomeComponent {
-					title: Some("Latest Updates".into()),
-					subtitle: None,
-					value: aidoku::HomeComponentValue::MangaChapterList {
-						page_size: Some(16),
-						entries: latest_updates,
-						listing: None,
-					},
+			});
+		}
+		components.extend([
+			HomeComponent {
+				title: Some("Hot Updates".into()),
+				subtitle: None,
+				value: aidoku::HomeComponentValue::MangaChapterList {
+					page_size: Some(6),
+					entries: hot_updates,
+					listing: Some(Listing {
+						id: "hot".into(),
+						name: "Hot Updates".into(),
+						..Default::default()
+					}),
 				},
-				HomeComponent {
-					title: Some("Recommendations".into()),
-					subtitle: None,
-					value: aidoku::HomeComponentValue::Scroller {
-						entries: recommendations,
-						listing: None,
-					},
+			},
+			HomeComponent {
+				title: Some("Latest Updates".into()),
+				subtitle: None,
+				value: aidoku::HomeComponentValue::MangaChapterList {
+					page_size: Some(16),
+					entries: latest_updates,
+					listing: Some(Listing {
+						id: "latest".into(),
+						name: "Latest Updates".into(),
+						..Default::default()
+					}),
 				},
-			],
-		})
+			},
+			HomeComponent {
+				title: Some("Recommendations".into()),
+				subtitle: None,
+				value: aidoku::HomeComponentValue::Scroller {
+					entries: recommendations,
+					listing: None,
+				},
+			},
+		]);
+
+		Ok(HomeLayout { components })
+	}
+}
+
+impl DynamicListings for WeebCentral {
+	fn get_dynamic_listings(&self) -> Result<Vec<Listing>> {
+		// subscriptions are only read from the site, since the app doesn't tell sources
+		// when a series is added to or removed from the library
+		if auth::is_logged_in() {
+			Ok(vec![
+				Listing {
+					id: SUBSCRIPTIONS_LISTING_ID.into(),
+					name: "Subscriptions".into(),
+					kind: ListingKind::Default,
+				},
+				Listing {
+					id: HISTORY_LISTING_ID.into(),
+					name: "Continue Reading".into(),
+					kind: ListingKind::Default,
+				},
+			])
+		} else {
+			Ok(Vec::new())
+		}
+	}
+}
+
+impl AlternateCoverProvider for WeebCentral {
+	fn get_alternate_covers(&self, manga: Manga) -> Result<Vec<String>> {
+		let html = Request::get(format!("{BASE_URL}{}", manga.key))?.checked_html()?;
+
+		// the cover is served in several formats through a picture element
+		let mut covers: Vec<String> = Vec::new();
+		if let Some(els) = html.select("section[x-data] > section picture > source[srcset]") {
+			// srcset is a list of "url descriptor" candidates separated by commas
+			covers.extend(els.filter_map(|el| el.attr("srcset")).flat_map(|srcset| {
+				srcset
+					.split(',')
+					.filter_map(|candidate| candidate.split_whitespace().next())
+					.map(|url| {
+						if url.starts_with('/') {
+							format!("{BASE_URL}{url}")
+						} else {
+							url.into()
+						}
+					})
+					.collect::<Vec<String>>()
+			}));
+		}
+		if let Some(els) = html.select("section[x-data] > section picture > img") {
+			covers.extend(els.filter_map(|el| el.attr("abs:src")));
+		}
+		let mut unique_covers: Vec<String> = Vec::new();
+		for cover in covers {
+			if !unique_covers.contains(&cover) {
+				unique_covers.push(cover);
+			}
+		}
+		Ok(unique_covers)
+	}
+}
+
+impl BasicLoginHandler for WeebCentral {
+	fn handle_basic_login(&self, _key: String, username: String, password: String) -> Result<bool> {
+		auth::login(&username, &password)
+	}
+}
+
+impl NotificationHandler for WeebCentral {
+	fn handle_notification(&self, notification: String) {
+		// handle log out
+		if notification == "login" && !auth::is_logged_in() {
+			// if the username/password default keys were removed, we should remove the cookie key
+			auth::logout();
+		}
 	}
 }
 
@@ -414,7 +622,7 @@ impl DeepLinkHandler for WeebCentral {
 			Ok(Some(DeepLinkResult::Manga { key: key.into() }))
 		} else if key.starts_with(CHAPTER_PATH) {
 			// ex: https://weebcentral.com/chapters/01JXNANGY619TDR9F4FST2M5E8
-			let html = Request::get(&url)?.html()?;
+			let html = Request::get(&url)?.checked_html()?;
 			let manga_key = html
 				.select_first("main a")
 				.and_then(|e| e.attr("href"))
@@ -431,4 +639,13 @@ impl DeepLinkHandler for WeebCentral {
 	}
 }
 
-register_source!(WeebCentral, ListingProvider, Home, DeepLinkHandler);
+register_source!(
+	WeebCentral,
+	ListingProvider,
+	Home,
+	DynamicListings,
+	AlternateCoverProvider,
+	BasicLoginHandler,
+	NotificationHandler,
+	DeepLinkHandler
+);
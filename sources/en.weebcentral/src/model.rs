@@ -1,3 +1,5 @@
+use aidoku::alloc::{String, Vec};
+
 pub enum SortOptions {
 	BestMatch,
 	Alphabet,
@@ -33,3 +35,138 @@ impl From<SortOptions> for &str {
 		}
 	}
 }
+
+pub enum SeriesStatus {
+	Ongoing,
+	Complete,
+	Hiatus,
+	Canceled,
+}
+
+impl TryFrom<&str> for SeriesStatus {
+	type Error = ();
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		match value {
+			"Ongoing" => Ok(SeriesStatus::Ongoing),
+			"Complete" => Ok(SeriesStatus::Complete),
+			"Hiatus" => Ok(SeriesStatus::Hiatus),
+			"Canceled" => Ok(SeriesStatus::Canceled),
+			_ => Err(()),
+		}
+	}
+}
+
+impl From<&SeriesStatus> for &str {
+	fn from(val: &SeriesStatus) -> Self {
+		match val {
+			SeriesStatus::Ongoing => "Ongoing",
+			SeriesStatus::Complete => "Complete",
+			SeriesStatus::Hiatus => "Hiatus",
+			SeriesStatus::Canceled => "Canceled",
+		}
+	}
+}
+
+pub enum SeriesType {
+	Manga,
+	Manhwa,
+	Manhua,
+	Oel,
+}
+
+impl TryFrom<&str> for SeriesType {
+	type Error = ();
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		match value {
+			"Manga" => Ok(SeriesType::Manga),
+			"Manhwa" => Ok(SeriesType::Manhwa),
+			"Manhua" => Ok(SeriesType::Manhua),
+			"OEL" => Ok(SeriesType::Oel),
+			_ => Err(()),
+		}
+	}
+}
+
+impl From<&SeriesType> for &str {
+	fn from(val: &SeriesType) -> Self {
+		match val {
+			SeriesType::Manga => "Manga",
+			SeriesType::Manhwa => "Manhwa",
+			SeriesType::Manhua => "Manhua",
+			SeriesType::Oel => "OEL",
+		}
+	}
+}
+
+// A yes/no search option that can also be left unset.
+#[derive(Default)]
+pub enum TriState {
+	#[default]
+	Any,
+	Included,
+	Excluded,
+}
+
+impl From<i32> for TriState {
+	fn from(value: i32) -> Self {
+		match value {
+			0 => TriState::Excluded,
+			1 => TriState::Included,
+			_ => TriState::Any,
+		}
+	}
+}
+
+impl From<&TriState> for &str {
+	fn from(val: &TriState) -> Self {
+		match val {
+			TriState::Any => "Any",
+			TriState::Included => "True",
+			TriState::Excluded => "False",
+		}
+	}
+}
+
+// Items of a multi-select filter, split into included and excluded values.
+pub struct IncludeExclude<T> {
+	pub included: Vec<T>,
+	pub excluded: Vec<T>,
+}
+
+impl<T> Default for IncludeExclude<T> {
+	fn default() -> Self {
+		Self {
+			included: Vec::new(),
+			excluded: Vec::new(),
+		}
+	}
+}
+
+impl<'a, T: TryFrom<&'a str>> IncludeExclude<T> {
+	pub fn from_values(included: &'a [String], excluded: &'a [String]) -> Self {
+		Self {
+			included: included
+				.iter()
+				.filter_map(|value| T::try_from(value.as_str()).ok())
+				.collect(),
+			excluded: excluded
+				.iter()
+				.filter_map(|value| T::try_from(value.as_str()).ok())
+				.collect(),
+		}
+	}
+}
+
+#[derive(Default)]
+pub struct SearchFilters {
+	pub text: Option<String>,
+	pub author: Option<String>,
+	pub sort: Option<(SortOptions, bool)>,
+	pub status: IncludeExclude<SeriesStatus>,
+	pub series_type: IncludeExclude<SeriesType>,
+	pub tags: IncludeExclude<String>,
+	pub official: TriState,
+	pub adult: TriState,
+}
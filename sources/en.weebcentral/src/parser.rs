@@ -0,0 +1,268 @@
+use crate::BASE_URL;
+use aidoku::{
+	alloc::{String, Vec},
+	imports::html::{Document, Element},
+	prelude::*,
+	Chapter, Manga, MangaWithChapter,
+};
+
+// chapter keys are always "/chapters/{id}", regardless of which list or link they came from
+pub fn chapter_key(url: &str) -> Option<String> {
+	let path = url.strip_prefix(BASE_URL).unwrap_or(url);
+	let id = path
+		.strip_prefix("/chapters/")?
+		.split(['/', '?', '#'])
+		.next()
+		.filter(|id| !id.is_empty())?;
+	Some(format!("/chapters/{id}"))
+}
+
+pub fn parse_chapter(element: &Element) -> Option<Chapter> {
+	let url = element
+		.select_first("a")
+		.and_then(|el| el.attr("abs:href"))?;
+
+	let key = chapter_key(&url)?;
+
+	let title = element
+		.select_first("span.flex > span")
+		.and_then(|el| el.text());
+
+	let mut chapter_number = title
+		.as_ref()
+		.and_then(|t| t.rsplit(' ').next())
+		.and_then(|num| num.parse::<f32>().ok());
+
+	let is_volume = title.as_ref().is_some_and(|t| t.contains("Volume"));
+	let is_chapter = title.as_ref().is_some_and(|t| t.contains("Chapter"));
+
+	let (final_title, volume_number) = match (is_volume, is_chapter) {
+		(true, _) => (None, chapter_number.take()),
+		(_, true) => (None, None),
+		_ => (title, None),
+	};
+
+	let date_uploaded = element
+		.select_first("time[datetime]")
+		.and_then(|el| el.attr("datetime"))
+		.and_then(|dt| chrono::DateTime::parse_from_rfc3339(&dt).ok())
+		.map(|d| d.timestamp());
+
+	Some(Chapter {
+		key,
+		title: final_title,
+		chapter_number,
+		volume_number,
+		date_uploaded,
+		url: Some(url),
+		..Default::default()
+	})
+}
+
+// the chapter list shown on a series page, which only has the most recent chapters for longer series
+pub struct SeriesChapters {
+	pub chapters: Vec<Chapter>,
+	pub full_list_url: Option<String>,
+	// whether the page is known to list every chapter
+	pub is_complete: bool,
+}
+
+pub fn parse_series_chapters(html: &Document) -> SeriesChapters {
+	let chapters = html
+		.select("#chapter-list div[x-data]:has(a[href*=/chapters/])")
+		.map(|els| els.filter_map(|el| parse_chapter(&el)).collect::<Vec<_>>())
+		.unwrap_or_default();
+	let full_list_url = html
+		.select_first("#chapter-list [hx-get*=full-chapter-list]")
+		.and_then(|el| el.attr("hx-get"));
+	// a missing "show all" button could also mean the markup changed, so the list is
+	// only trusted when it goes back to the first chapter
+	let is_complete = full_list_url.is_none()
+		&& chapters
+			.last()
+			.and_then(|chapter| chapter.chapter_number)
+			.is_some_and(|number| number <= 1.0);
+	SeriesChapters {
+		chapters,
+		full_list_url,
+		is_complete,
+	}
+}
+
+// merges the recent chapters into the known ones, replacing the window they cover
+//
+// known chapters inside that window are dropped, so removed chapters and corrected
+// dates are picked up. returns `None` when the two lists don't overlap, since
+// chapters could be missing between them.
+pub fn merge_chapters(recent: Vec<Chapter>, known: Vec<Chapter>) -> Option<Vec<Chapter>> {
+	// the oldest recent chapter that's also known marks the end of the window
+	let window_end = known
+		.iter()
+		.rposition(|chapter| recent.iter().any(|c| c.key == chapter.key))?;
+	let mut chapters = recent;
+	for chapter in known.into_iter().skip(window_end + 1) {
+		if !chapters.iter().any(|c| c.key == chapter.key) {
+			chapters.push(chapter);
+		}
+	}
+	Some(chapters)
+}
+
+pub fn parse_full_chapter_list(html: &Document) -> Vec<Chapter> {
+	html.select("div[x-data]")
+		.map(|els| els.filter_map(|el| parse_chapter(&el)).collect::<Vec<_>>())
+		.unwrap_or_default()
+}
+
+// manga cards used by the subscription and listing pages
+pub fn parse_manga_card(element: &Element) -> Option<Manga> {
+	let link = element.select_first("a[href*=/series/]")?;
+	let key = link.attr("abs:href")?.strip_prefix(BASE_URL)?.into();
+	let img = element.select_first("img");
+	let cover = img.as_ref().and_then(|el| el.attr("abs:src"));
+	let title = element
+		.select_first(".text-lg, strong")
+		.and_then(|el| el.text())
+		.or_else(|| {
+			img.and_then(|el| el.attr("alt"))
+				.map(|alt| alt.trim_end_matches(" cover").into())
+		})?;
+	Some(Manga {
+		key,
+		title,
+		cover,
+		..Default::default()
+	})
+}
+
+pub fn parse_related_series(html: &Document) -> Vec<Manga> {
+	html.select("li:has(strong:contains(Related Series)) li a[href*=/series/]")
+		.map(|els| {
+			els.filter_map(|el| {
+				let key = el.attr("abs:href")?.strip_prefix(BASE_URL)?.into();
+				let title = el.text()?;
+				Some(Manga {
+					key,
+					title,
+					..Default::default()
+				})
+			})
+			.collect::<Vec<_>>()
+		})
+		.unwrap_or_default()
+}
+
+// manga with their latest chapter, used by the home page sections
+pub fn parse_manga_with_chapter(el: &Element) -> Option<MangaWithChapter> {
+	let mut links = el.select("a")?;
+	let manga_key = links.first()?.attr("href")?.strip_prefix(BASE_URL)?.into();
+	let chapter_link = links.next_back()?;
+	let chapter_key = chapter_link.attr("href")?.strip_prefix(BASE_URL)?.into();
+	let cover = el.select_first("img")?.attr("src");
+	let title = el.select_first(".text-lg")?.text()?;
+	let chapter_number = chapter_link
+		.select_first("div.flex")?
+		.text()
+		.as_ref()
+		.and_then(|t| t.rsplit(' ').next())
+		.and_then(|num| num.parse::<f32>().ok());
+	let date_uploaded = el
+		.select_first("time[datetime]")
+		.and_then(|el| el.attr("datetime"))
+		.and_then(|dt| chrono::DateTime::parse_from_rfc3339(&dt).ok())
+		.map(|d| d.timestamp());
+	Some(MangaWithChapter {
+		manga: Manga {
+			key: manga_key,
+			title,
+			cover,
+			..Default::default()
+		},
+		chapter: Chapter {
+			key: chapter_key,
+			chapter_number,
+			date_uploaded,
+			..Default::default()
+		},
+	})
+}
+
+// the home page's continue reading section, only shown to logged in users
+pub fn parse_continue_reading(html: &Document) -> Vec<MangaWithChapter> {
+	html.select("section:has(h2:contains(Continue Reading)) article")
+		.map(|els| {
+			els.filter_map(|el| parse_manga_with_chapter(&el))
+				.collect::<Vec<_>>()
+		})
+		.unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use aidoku::alloc::vec;
+	use aidoku_test::aidoku_test;
+
+	fn chapter(key: &str) -> Chapter {
+		Chapter {
+			key: key.into(),
+			..Default::default()
+		}
+	}
+
+	fn keys(chapters: &[Chapter]) -> Vec<&str> {
+		chapters.iter().map(|c| c.key.as_str()).collect()
+	}
+
+	#[aidoku_test]
+	fn chapter_key_is_stable() {
+		let expected = Some(String::from("/chapters/01JXNANGY619TDR9F4FST2M5E8"));
+		assert_eq!(
+			chapter_key("https://weebcentral.com/chapters/01JXNANGY619TDR9F4FST2M5E8"),
+			expected
+		);
+		assert_eq!(
+			chapter_key(
+				"https://weebcentral.com/chapters/01JXNANGY619TDR9F4FST2M5E8/images?is_prev=False"
+			),
+			expected
+		);
+		assert_eq!(
+			chapter_key("/chapters/01JXNANGY619TDR9F4FST2M5E8#top"),
+			expected
+		);
+		assert_eq!(
+			chapter_key("https://weebcentral.com/series/01J76XYEZYBE7Y3MEY7AEQ8MQN"),
+			None
+		);
+		assert_eq!(chapter_key("/chapters/"), None);
+	}
+
+	#[aidoku_test]
+	fn merge_replaces_overlapping_window() {
+		let recent = vec![
+			chapter("/chapters/5"),
+			chapter("/chapters/4"),
+			chapter("/chapters/2"),
+		];
+		let known = vec![
+			chapter("/chapters/4"),
+			chapter("/chapters/3"),
+			chapter("/chapters/2"),
+			chapter("/chapters/1"),
+		];
+		let merged = merge_chapters(recent, known).unwrap();
+		// chapter 3 was removed from the site, chapter 1 is older than the window
+		assert_eq!(
+			keys(&merged),
+			["/chapters/5", "/chapters/4", "/chapters/2", "/chapters/1"]
+		);
+	}
+
+	#[aidoku_test]
+	fn merge_without_overlap() {
+		let recent = vec![chapter("/chapters/9"), chapter("/chapters/8")];
+		let known = vec![chapter("/chapters/2"), chapter("/chapters/1")];
+		assert!(merge_chapters(recent, known).is_none());
+	}
+}
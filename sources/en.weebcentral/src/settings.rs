@@ -0,0 +1,11 @@
+use aidoku::{alloc::String, imports::defaults::defaults_get};
+
+const FETCH_LIMIT_KEY: &str = "fetchLimit";
+const DEFAULT_FETCH_LIMIT: i32 = 24;
+
+pub fn get_fetch_limit() -> i32 {
+	defaults_get::<String>(FETCH_LIMIT_KEY)
+		.and_then(|limit| limit.parse::<i32>().ok())
+		.filter(|limit| *limit > 0)
+		.unwrap_or(DEFAULT_FETCH_LIMIT)
+}
@@ -17,8 +17,11 @@ impl Impl for RawOtaku {
 			search_path: "".into(),
 			search_param: "q".into(),
 			page_param: "p".into(),
-			get_chapter_selector: || "#ja-chaps > li".into(),
-			get_chapter_language: |_| "ja".into(),
+			filter_keyword_param: Some("q".into()),
+			genre_exclude_param: Some("genres_exclude".into()),
+			languages: Cow::Borrowed(&["ja"]),
+			get_chapter_selector: |lang| format!("#{lang}-chaps > li"),
+			get_volume_selector: |_| None,
 			get_page_url_path: |chapter_id| format!("/json/chapter?id={chapter_id}&mode=vertical"),
 			set_default_filters: |query_params| {
 				query_params.set("type", Some("all"));
@@ -0,0 +1,31 @@
+#![no_std]
+use aidoku::{alloc::borrow::Cow, prelude::*, Source};
+use mangareader::{Impl, MangaReader, Params};
+
+const BASE_URL: &str = "https://mangareader.to";
+
+struct MangaReaderTo;
+
+impl Impl for MangaReaderTo {
+	fn new() -> Self {
+		Self
+	}
+
+	fn params(&self) -> Params {
+		Params {
+			base_url: BASE_URL.into(),
+			page_selector: ".container-reader-chapter > div.iv-card".into(),
+			languages: Cow::Borrowed(&["en", "ja", "ko", "zh", "fr"]),
+			..Default::default()
+		}
+	}
+}
+
+register_source!(
+	MangaReader<MangaReaderTo>,
+	ListingProvider,
+	Home,
+	ImageRequestProvider,
+	PageImageProcessor,
+	DeepLinkHandler
+);
@@ -4,6 +4,7 @@ use aidoku::{
 	helpers::uri::QueryParameters,
 	imports::{
 		canvas::{Canvas, ImageRef, Rect},
+		defaults::defaults_get,
 		error::AidokuError,
 		net::Request,
 		std::send_partial_result,
@@ -73,25 +74,40 @@ pub trait Impl {
 		let html = Request::get(&url)?.authed().html()?;
 
 		if needs_details {
-			let element = html
-				.select_first("section.series-information div.series-header")
-				.ok_or(AidokuError::message("漫画の情報がありません"))?;
-			let title = element
-				.select_first("h1.series-header-title")
-				.and_then(|e| e.text())
-				.unwrap_or(new_manga.title.clone());
-			let cover = html
-				.select_first("div.series-header-image-wrapper img")
-				.and_then(|e| e.attr("data-src"));
-			let authors = element
-				.select_first("h2.series-header-author")
-				.and_then(|e| {
-					let text = e.text()?;
-					Some(text.split('/').map(String::from).collect::<Vec<_>>())
-				});
-			let description = element
-				.select_first("p.series-header-description")
-				.and_then(|e| e.text());
+			let element = html.select_first("section.series-information div.series-header");
+			let (title, cover, authors, description) = if let Some(element) = element {
+				let title = element
+					.select_first("h1.series-header-title")
+					.and_then(|e| e.text())
+					.unwrap_or(new_manga.title.clone());
+				let cover = html
+					.select_first("div.series-header-image-wrapper img")
+					.and_then(|e| e.attr("data-src"));
+				let authors = element
+					.select_first("h2.series-header-author")
+					.and_then(|e| {
+						let text = e.text()?;
+						Some(text.split('/').map(String::from).collect::<Vec<_>>())
+					});
+				let description = element
+					.select_first("p.series-header-description")
+					.and_then(|e| e.text());
+				(title, cover, authors, description)
+			} else if ReadableProductType::from_key(&manga.key) != ReadableProductType::Episode {
+				// magazine issue and volume pages don't have a series header
+				let meta = |property: &str| {
+					html.select_first(format!("meta[property=\"{property}\"]"))
+						.and_then(|e| e.attr("content"))
+				};
+				(
+					meta("og:title").unwrap_or(new_manga.title.clone()),
+					meta("og:image"),
+					None,
+					meta("og:description"),
+				)
+			} else {
+				return Err(AidokuError::message("漫画の情報がありません"));
+			};
 			let is_scroll = html
 				.select_first("#content")
 				.map(|e| e.has_class("content-vertical")) // has content-horizontal normally
@@ -116,45 +132,34 @@ pub trait Impl {
 		}
 
 		if needs_chapters {
-			let target_endpoint = {
-				let aggregate_id = html
-					.select_first("script.js-valve")
-					.and_then(|el| el.attr("data-giga_series"))
-					.unwrap_or_else(|| {
-						html.select_first(".readable-products-pagination")
-							.and_then(|el| el.attr("data-aggregate-id"))
-							.unwrap_or_default()
-					});
-
-				let mut qs = QueryParameters::new();
-				qs.push("aggregate_id", Some(&aggregate_id));
-				qs.push("number_since", Some("2147483647")); // i32 max
-				qs.push("number_until", Some("0"));
-				qs.push("read_more_num", Some("150"));
-				qs.push("type", Some("episode"));
+			let aggregate_id = html
+				.select_first("script.js-valve")
+				.and_then(|el| el.attr("data-giga_series"))
+				.unwrap_or_else(|| {
+					html.select_first(".readable-products-pagination")
+						.and_then(|el| el.attr("data-aggregate-id"))
+						.unwrap_or_default()
+				});
 
-				format!("{}/api/viewer/readable_products?{qs}", params.base_url)
-			};
+			// magazine issues are listed on their own, while series can also have volumes
+			let product_types: &[ReadableProductType] =
+				match ReadableProductType::from_key(&manga.key) {
+					ReadableProductType::Magazine => &[ReadableProductType::Magazine],
+					_ if defaults_get::<bool>("showVolumes").unwrap_or(true) => {
+						&[ReadableProductType::Episode, ReadableProductType::Volume]
+					}
+					_ => &[ReadableProductType::Episode],
+				};
 
-			let mut json = Request::get(target_endpoint)?
-				.header("Referer", &url)
-				.authed()
-				.json_owned::<GigaReadMoreResponse>();
 			let mut chapters: Vec<Chapter> = Vec::new();
-
-			while let Ok(ok_json) = json {
-				if let Some(new_chapters) = parser::parse_chapter_elements(
-					ok_json.html,
-					&params.base_url,
+			for product_type in product_types {
+				chapters.extend(get_readable_products(
+					params,
+					&url,
+					&aggregate_id,
+					*product_type,
 					&new_manga.title,
-					&params.chapter_list_selector,
-				) {
-					chapters.extend(new_chapters);
-				}
-				json = Request::get(ok_json.next_url)?
-					.header("Referer", &url)
-					.authed()
-					.json_owned::<GigaReadMoreResponse>();
+				)?);
 			}
 
 			new_manga.chapters = Some(chapters);
@@ -291,17 +296,65 @@ pub trait Impl {
 			return Ok(None);
 		};
 
-		const EPISODE_PATH: &str = "episode/";
+		const READABLE_PATHS: [&str; 3] = ["/episode/", "/volume/", "/magazine/"];
 
-		if let Some(key) = path.strip_prefix(EPISODE_PATH) {
+		if READABLE_PATHS.iter().any(|prefix| path.starts_with(prefix)) {
 			// ex: https://shonenjumpplus.com/episode/10834108156648240735
+			// ex: https://comic-days.com/magazine/3269754496560134260
 			// the manga key can be any of the chapter keys
 			Ok(Some(DeepLinkResult::Chapter {
-				manga_key: key.into(),
-				key: key.into(),
+				manga_key: path.into(),
+				key: path.into(),
 			}))
 		} else {
 			Ok(None)
 		}
 	}
 }
+
+// fetch every page of a readable product list (episodes, volumes or magazine issues)
+fn get_readable_products(
+	params: &Params,
+	referer: &str,
+	aggregate_id: &str,
+	product_type: ReadableProductType,
+	manga_title: &str,
+) -> Result<Vec<Chapter>> {
+	let target_endpoint = {
+		let mut qs = QueryParameters::new();
+		qs.push("aggregate_id", Some(aggregate_id));
+		qs.push("number_since", Some("2147483647")); // i32 max
+		qs.push("number_until", Some("0"));
+		qs.push("read_more_num", Some("150"));
+		qs.push("type", Some(product_type.as_str()));
+
+		format!("{}/api/viewer/readable_products?{qs}", params.base_url)
+	};
+
+	let mut json = Request::get(target_endpoint)?
+		.header("Referer", referer)
+		.authed()
+		.json_owned::<GigaReadMoreResponse>();
+	let mut chapters: Vec<Chapter> = Vec::new();
+
+	while let Ok(ok_json) = json {
+		if let Some(new_chapters) = parser::parse_chapter_elements(
+			ok_json.html,
+			&params.base_url,
+			manga_title,
+			&params.chapter_list_selector,
+			product_type,
+		) {
+			chapters.extend(new_chapters);
+		}
+		if ok_json.next_url.is_empty() {
+			break;
+		}
+		json = Request::get(ok_json.next_url)?
+			.header("Referer", referer)
+			.authed()
+			.json_owned::<GigaReadMoreResponse>();
+	}
+
+	Ok(chapters)
+}
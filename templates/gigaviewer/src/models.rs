@@ -32,3 +32,31 @@ pub struct GigaReadMoreResponse {
 	pub html: String,
 	pub next_url: String,
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReadableProductType {
+	Episode,
+	Volume,
+	Magazine,
+}
+
+impl ReadableProductType {
+	// determine the product type from a key like "/volume/123"
+	pub fn from_key(key: &str) -> Self {
+		if key.starts_with("/magazine/") {
+			Self::Magazine
+		} else if key.starts_with("/volume/") {
+			Self::Volume
+		} else {
+			Self::Episode
+		}
+	}
+
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Episode => "episode",
+			Self::Volume => "volume",
+			Self::Magazine => "magazine",
+		}
+	}
+}
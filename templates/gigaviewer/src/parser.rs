@@ -1,9 +1,10 @@
+use crate::models::ReadableProductType;
 use aidoku::{
-	alloc::{String, Vec},
+	alloc::{string::ToString, String, Vec},
 	helpers::date::parse_local_date,
 	imports::{
 		defaults::defaults_get,
-		html::{Document, Html},
+		html::{Document, Element, Html},
 	},
 	prelude::*,
 	Chapter, Manga,
@@ -60,21 +61,24 @@ pub fn parse_chapter_elements(
 	base_url: &str,
 	manga_title: &str,
 	chapter_list_selector: &str,
+	product_type: ReadableProductType,
 ) -> Option<Vec<Chapter>> {
 	let document = Html::parse(html).ok()?;
 	let skip_locked = !defaults_get::<bool>("showLocked").unwrap_or(true);
+	let only_free = defaults_get::<bool>("showOnlyFree").unwrap_or(false);
 	document
 		.select(format!("ul.series-episode-list {}", chapter_list_selector))
 		.map(|episodes| {
-			let mut chapters = episodes
+			let (mut chapters, labels): (Vec<_>, Vec<_>) = episodes
 				.filter_map(|e| {
 					let date_uploaded = e
 						.select_first("span.series-episode-list-date")
 						.and_then(|e| parse_local_date(e.text()?, "%Y/%m/%d"));
 
 					let locked = e.select_first(".series-episode-list-price").is_some();
+					let pricing = EpisodePricing::parse(&e);
 
-					if skip_locked && locked {
+					if (skip_locked && locked) || (only_free && !pricing.is_free(locked)) {
 						return None;
 					}
 
@@ -90,25 +94,39 @@ pub fn parse_chapter_elements(
 					let title = info
 						.select_first("h4.series-episode-list-title")
 						.and_then(|e| e.text());
-					let chapter_number = title.clone().and_then(parse_chapter_number);
+					// volumes are numbered separately from episodes, and magazine issue titles
+					// contain dates rather than a usable number
+					let (chapter_number, volume_number) = match product_type {
+						ReadableProductType::Episode => {
+							(title.clone().and_then(parse_chapter_number), None)
+						}
+						ReadableProductType::Volume => {
+							(None, title.clone().and_then(parse_chapter_number))
+						}
+						ReadableProductType::Magazine => (None, None),
+					};
 					let thumbnail = info
 						.select_first(".series-episode-list-thumb-container img")
 						.and_then(|e| e.attr("src"));
 
-					Some(Chapter {
-						key,
-						title,
-						chapter_number,
-						date_uploaded,
-						url: Some(url),
-						thumbnail,
-						locked,
-						..Default::default()
-					})
+					Some((
+						Chapter {
+							key,
+							title,
+							chapter_number,
+							volume_number,
+							date_uploaded,
+							url: Some(url),
+							thumbnail,
+							locked,
+							..Default::default()
+						},
+						pricing.label(),
+					))
 				})
-				.collect::<Vec<_>>();
+				.unzip();
 			// check for oneshot
-			if chapters.len() == 1 {
+			if product_type == ReadableProductType::Episode && chapters.len() == 1 {
 				let only_chapter_has_manga_title = chapters[0]
 					.title
 					.as_ref()
@@ -118,10 +136,89 @@ pub fn parse_chapter_elements(
 					chapters[0].chapter_number = Some(1.0);
 				}
 			}
+			// append free period and price info after the oneshot check, since it compares titles
+			for (chapter, label) in chapters.iter_mut().zip(labels) {
+				if let Some(label) = label {
+					chapter.title = Some(match chapter.title.take() {
+						Some(title) => format!("{title}（{label}）"),
+						None => label,
+					});
+				}
+			}
 			chapters
 		})
 }
 
+// free period and point cost shown on an episode list item
+struct EpisodePricing {
+	free_until: Option<String>,
+	initial_free: bool,
+	price: Option<i32>,
+}
+
+impl EpisodePricing {
+	fn parse(e: &Element) -> Self {
+		let free_until = e
+			.select_first(".series-episode-list-free-until, .series-episode-list-free-term")
+			.and_then(|e| e.text())
+			.map(|text| {
+				// e.g. "2024/10/31まで無料" or "〜10/31 23:59"
+				text.chars()
+					.skip_while(|c| !c.is_ascii_digit())
+					.take_while(|c| c.is_ascii_digit() || matches!(c, '/' | ':' | ' '))
+					.collect::<String>()
+					.trim()
+					.to_string()
+			})
+			.filter(|date| !date.is_empty());
+		// only the price element is checked, since episode titles can contain the same text
+		let price_text = e
+			.select_first(".series-episode-list-price")
+			.and_then(|e| e.text());
+		let initial_free =
+			e.select_first(".series-episode-list-initial-free")
+				.is_some() || price_text
+				.as_ref()
+				.is_some_and(|text| text.contains("初回無料"));
+		let price = price_text
+			.and_then(|text| {
+				text.chars()
+					.filter(|c| c.is_ascii_digit())
+					.collect::<String>()
+					.parse::<i32>()
+					.ok()
+			})
+			.filter(|price| *price > 0);
+		Self {
+			free_until,
+			initial_free,
+			price,
+		}
+	}
+
+	fn is_free(&self, locked: bool) -> bool {
+		!locked || self.initial_free || self.free_until.is_some()
+	}
+
+	fn label(&self) -> Option<String> {
+		let mut parts = Vec::new();
+		if let Some(free_until) = &self.free_until {
+			parts.push(format!("{free_until}まで無料"));
+		}
+		if self.initial_free {
+			parts.push("初回無料".into());
+		}
+		if let Some(price) = self.price {
+			parts.push(format!("{price}pt"));
+		}
+		if parts.is_empty() {
+			None
+		} else {
+			Some(parts.join("・"))
+		}
+	}
+}
+
 // Parse chapter number from title string containing japanese characters
 pub fn parse_chapter_number(title_str: String) -> Option<f32> {
 	let mut digits = String::new();
@@ -203,3 +300,50 @@ pub fn parse_chapter_number(title_str: String) -> Option<f32> {
 		num
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use aidoku_test::aidoku_test;
+
+	#[aidoku_test]
+	fn pricing_label() {
+		let pricing = EpisodePricing {
+			free_until: Some("2024/10/31".into()),
+			initial_free: true,
+			price: Some(30),
+		};
+		assert_eq!(
+			pricing.label().as_deref(),
+			Some("2024/10/31まで無料・初回無料・30pt")
+		);
+		let pricing = EpisodePricing {
+			free_until: None,
+			initial_free: false,
+			price: None,
+		};
+		assert_eq!(pricing.label(), None);
+	}
+
+	#[aidoku_test]
+	fn pricing_is_free() {
+		let paid = EpisodePricing {
+			free_until: None,
+			initial_free: false,
+			price: Some(50),
+		};
+		assert!(!paid.is_free(true));
+		assert!(paid.is_free(false));
+		let campaign = EpisodePricing {
+			free_until: Some("10/31 23:59".into()),
+			..paid
+		};
+		assert!(campaign.is_free(true));
+	}
+
+	#[aidoku_test]
+	fn chapter_numbers() {
+		assert_eq!(parse_chapter_number("第12話".into()), Some(12.0));
+		assert_eq!(parse_chapter_number("第１２話".into()), Some(12.0));
+	}
+}
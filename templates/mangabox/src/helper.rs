@@ -1,10 +1,24 @@
-use crate::Params;
+use crate::{
+	models::{Genre, GenreCache},
+	Params,
+};
 use aidoku::{
-	alloc::{String, Vec},
+	alloc::{string::ToString, vec, String, Vec},
+	helpers::{date::parse_local_date, uri::QueryParameters},
+	imports::{
+		defaults::{defaults_get, defaults_set, DefaultValue},
+		html::{Document, Element},
+		net::Request,
+		std::current_date,
+	},
 	prelude::*,
-	FilterValue, MangaStatus,
+	FilterValue, Manga, MangaStatus, Result,
 };
 
+const IMAGE_SERVER_KEY: &str = "imageServer";
+const GENRE_CACHE_KEY: &str = "genres.cache";
+const GENRE_CACHE_DURATION: i64 = 60 * 60 * 24 * 7; // one week
+
 pub fn status_from_string(string: &str) -> MangaStatus {
 	let string = string.trim();
 	let status = string
@@ -20,6 +34,63 @@ pub fn status_from_string(string: &str) -> MangaStatus {
 	}
 }
 
+// parses a manga item from search results and listings
+pub fn parse_manga_item(params: &Params, item: &Element) -> Option<Manga> {
+	let title = item
+		.select_first(".story_name")
+		.and_then(|el| el.text())
+		.unwrap_or_else(|| {
+			item.select_first("a")
+				.and_then(|a| a.attr("title"))
+				.unwrap_or_default()
+		});
+	let url = item.select_first("a")?.attr("href")?;
+	let key = url
+		.strip_prefix(params.base_url.as_ref())
+		.unwrap_or(&url)
+		.into();
+	let cover = item.select_first("img").and_then(|img| img.attr("src"));
+	Some(Manga {
+		key,
+		cover,
+		title,
+		url: Some(url),
+		..Default::default()
+	})
+}
+
+pub fn parse_manga_list(params: &Params, html: &Document) -> Vec<Manga> {
+	html.select(params.item_selector.as_ref())
+		.map(|els| {
+			els.filter_map(|item| parse_manga_item(params, &item))
+				.collect::<Vec<_>>()
+		})
+		.unwrap_or_default()
+}
+
+// search filters for a listing id, also used by the home page sections that link to them
+pub fn get_listing_filters(id: &str) -> Option<Vec<FilterValue>> {
+	let sort = |index: i32| FilterValue::Sort {
+		id: "sort".into(),
+		index,
+		ascending: false,
+	};
+	match id {
+		"new" => Some(vec![sort(0)]),
+		"latest" => Some(vec![sort(1)]),
+		"hot" => Some(vec![sort(2)]),
+		// completed series ordered by views
+		"completed" => Some(vec![
+			sort(2),
+			FilterValue::Select {
+				id: "status".into(),
+				value: "Completed".into(),
+			},
+		]),
+		_ => None,
+	}
+}
+
 // parses ".../chapter-x" where x can be e.g. "3" or "0-2" for decimals
 pub fn get_chapter_number(s: &str) -> Option<f32> {
 	const PREFIX: &str = "chapter-";
@@ -34,35 +105,149 @@ pub fn get_chapter_number(s: &str) -> Option<f32> {
 	None
 }
 
-pub fn strip_default_chapter_title(s: String) -> String {
-	const PREFIX: &str = "Chapter ";
+// extra chapters that shouldn't take a chapter number from their url
+const EXTRA_KEYWORDS: [&str; 8] = [
+	"extra",
+	"side story",
+	"special",
+	"bonus",
+	"omake",
+	"notice",
+	"announcement",
+	"hiatus",
+];
 
-	s[PREFIX.len()..]
-		.find(' ')
-		.map(|space_pos| s[PREFIX.len() + space_pos..].into())
-		.unwrap_or(s)
+pub struct ChapterInfo {
+	pub title: Option<String>,
+	pub chapter_number: Option<f32>,
+	pub volume_number: Option<f32>,
 }
 
+// parses chapter names like "Vol.3 Chapter 12.5: Title", "Chapter 7 - Title" or "Side Story 2"
+pub fn parse_chapter_info(text: &str, url: &str) -> ChapterInfo {
+	// splits a leading number off of a string, returning the number and the rest
+	fn split_number(s: &str) -> (Option<f32>, &str) {
+		let s = s.trim_start();
+		let end = s
+			.find(|c: char| !c.is_ascii_digit() && c != '.')
+			.unwrap_or(s.len());
+		let number = s[..end].trim_end_matches('.').parse::<f32>().ok();
+		(number, &s[end..])
+	}
+	// strips one of the given prefixes, ignoring case
+	fn strip_prefix_ignore_case<'a>(s: &'a str, prefixes: &[&str]) -> Option<&'a str> {
+		prefixes.iter().find_map(|prefix| {
+			s.get(..prefix.len())
+				.filter(|start| start.eq_ignore_ascii_case(prefix))
+				.map(|_| &s[prefix.len()..])
+		})
+	}
+
+	let mut rest = text.trim();
+
+	let mut volume_number = None;
+	if let Some(after) = strip_prefix_ignore_case(rest, &["volume", "vol.", "vol"]) {
+		let (number, after) = split_number(after);
+		if number.is_some() {
+			volume_number = number;
+			rest = after.trim_start();
+		}
+	}
+
+	let mut chapter_number = None;
+	if let Some(after) = strip_prefix_ignore_case(rest, &["chapter", "ch."]) {
+		let (number, after) = split_number(after);
+		if number.is_some() {
+			chapter_number = number;
+			rest = after;
+		}
+	}
+
+	let title = rest
+		.trim_start_matches(|c: char| c == ':' || c == '-' || c.is_whitespace())
+		.trim();
+	let title = if title.is_empty() {
+		None
+	} else {
+		Some(String::from(title))
+	};
+
+	if chapter_number.is_none() {
+		let lowercase_title = title.as_deref().unwrap_or_default().to_lowercase();
+		let is_extra = EXTRA_KEYWORDS
+			.iter()
+			.any(|keyword| lowercase_title.contains(keyword));
+		if !is_extra {
+			chapter_number = get_chapter_number(url);
+		}
+	}
+
+	ChapterInfo {
+		title,
+		chapter_number,
+		volume_number,
+	}
+}
+
+// parses dates shown as text, either relative ("2 hours ago") or absolute ("Jan 05,2024")
+pub fn parse_date(text: &str) -> Option<i64> {
+	let text = text.trim();
+	let lowercase_text = text.to_lowercase();
+	let now = current_date() as i64;
+
+	if lowercase_text == "just now" {
+		return Some(now);
+	}
+	if lowercase_text == "yesterday" {
+		return Some(now - 60 * 60 * 24);
+	}
+
+	if let Some(relative) = lowercase_text.strip_suffix("ago") {
+		let mut parts = relative.split_whitespace();
+		let amount = match parts.next()? {
+			"a" | "an" => 1,
+			number => number.parse::<i64>().ok()?,
+		};
+		let unit = parts.next()?;
+		let seconds = if unit.starts_with("sec") {
+			1
+		} else if unit.starts_with("min") {
+			60
+		} else if unit.starts_with("hour") {
+			60 * 60
+		} else if unit.starts_with("day") {
+			60 * 60 * 24
+		} else if unit.starts_with("week") {
+			60 * 60 * 24 * 7
+		} else if unit.starts_with("month") {
+			60 * 60 * 24 * 30
+		} else if unit.starts_with("year") {
+			60 * 60 * 24 * 365
+		} else {
+			return None;
+		};
+		return Some(now - amount * seconds);
+	}
+
+	["%b %d,%Y", "%b %d, %Y", "%b-%d-%Y", "%b-%d-%Y %H:%M"]
+		.iter()
+		.find_map(|format| parse_local_date(text, format))
+}
+
+// returns None for genres that can't be resolved to an id, since searching
+// without them would list every series instead
 pub fn get_search_url(
 	params: &Params,
 	query: Option<String>,
 	page: i32,
 	filters: Vec<FilterValue>,
-) -> String {
-	if let Some(query) = query {
-		// if there's a query, we can't filter
-		return format!(
-			"{}{}/{}?page={page}",
-			params.base_url,
-			params.search_path,
-			encode(query, '_')
-		);
-	}
-
+) -> Option<String> {
+	#[derive(PartialEq)]
 	enum SortOption {
 		Newest,
 		Latest,
 		TopRead,
+		Alphabetical,
 	}
 	enum StatusOption {
 		All,
@@ -71,7 +256,10 @@ pub fn get_search_url(
 	}
 	let mut sort = SortOption::Newest;
 	let mut status = StatusOption::All;
-	let mut genre = String::from("all");
+	let mut keyword_type = String::from("all");
+	let mut included_genres: Vec<String> = Vec::new();
+	let mut excluded_genres: Vec<String> = Vec::new();
+	let mut filtered = false;
 
 	for filter in filters {
 		match filter {
@@ -80,30 +268,110 @@ pub fn get_search_url(
 					0 => SortOption::Newest,
 					1 => SortOption::Latest,
 					2 => SortOption::TopRead,
+					3 => SortOption::Alphabetical,
 					_ => SortOption::Newest,
 				};
+				filtered |= sort != SortOption::Newest;
 			}
 			FilterValue::Select { id, value } => match id.as_str() {
 				"status" => match value.as_str() {
-					"Completed" => status = StatusOption::Completed,
-					"Ongoing" => status = StatusOption::Ongoing,
+					"Completed" => {
+						status = StatusOption::Completed;
+						filtered = true;
+					}
+					"Ongoing" => {
+						status = StatusOption::Ongoing;
+						filtered = true;
+					}
 					_ => {}
 				},
+				"keyword_type" => {
+					keyword_type = value;
+				}
+				// tag search
 				"genre" => {
-					genre = encode(value, '-');
+					let ids = resolve_genre_ids(params, [value]);
+					if ids.is_empty() {
+						return None;
+					}
+					included_genres.extend(ids);
+					filtered = true;
 				}
 				_ => {}
 			},
-			FilterValue::Text { value, .. } => {
-				// author search
-				return format!("{}/author/{}", params.base_url, encode(value, '-'));
+			FilterValue::MultiSelect {
+				id,
+				included,
+				excluded,
+			} if id == "genre" => {
+				let count = included.len();
+				let ids = resolve_genre_ids(params, included);
+				if ids.len() < count {
+					return None;
+				}
+				included_genres.extend(ids);
+				excluded_genres.extend(resolve_genre_ids(params, excluded));
+				filtered |= !included_genres.is_empty() || !excluded_genres.is_empty();
+			}
+			FilterValue::Text { id, value } if id == "author" => {
+				return Some(format!(
+					"{}{}/{}?page={page}",
+					params.base_url,
+					params.author_path,
+					encode(value, '-')
+				));
 			}
 			_ => {}
 		}
 	}
 
+	let use_advanced_search = !included_genres.is_empty()
+		|| !excluded_genres.is_empty()
+		|| sort == SortOption::Alphabetical
+		|| (query.is_some() && (filtered || keyword_type != "all"));
+
+	if use_advanced_search {
+		// the advanced search takes genre ids wrapped in underscores, e.g. "_2_12_"
+		let mut qs = QueryParameters::new();
+		qs.push("s", Some(&keyword_type));
+		if !included_genres.is_empty() {
+			qs.push("g_i", Some(&format!("_{}_", included_genres.join("_"))));
+		}
+		if !excluded_genres.is_empty() {
+			qs.push("g_e", Some(&format!("_{}_", excluded_genres.join("_"))));
+		}
+		match status {
+			StatusOption::All => {}
+			StatusOption::Completed => qs.push("sts", Some("completed")),
+			StatusOption::Ongoing => qs.push("sts", Some("ongoing")),
+		}
+		match sort {
+			SortOption::Latest => {}
+			SortOption::Newest => qs.push("orby", Some("newest")),
+			SortOption::TopRead => qs.push("orby", Some("topview")),
+			SortOption::Alphabetical => qs.push("orby", Some("az")),
+		}
+		if let Some(query) = query {
+			qs.push("keyw", Some(&encode(query, '_')));
+		}
+		qs.push("page", Some(&page.to_string()));
+		return Some(format!(
+			"{}{}?{qs}",
+			params.base_url, params.advanced_search_path
+		));
+	}
+
+	if let Some(query) = query {
+		return Some(format!(
+			"{}{}/{}?page={page}",
+			params.base_url,
+			params.search_path,
+			encode(query, '_')
+		));
+	}
+
 	let url_filter = match sort {
-		SortOption::Newest => 1,
+		SortOption::Newest | SortOption::Alphabetical => 1,
 		SortOption::Latest => 4,
 		SortOption::TopRead => 7,
 	} + match status {
@@ -111,10 +379,10 @@ pub fn get_search_url(
 		StatusOption::Completed => 1,
 		StatusOption::Ongoing => 2,
 	};
-	format!(
-		"{}/genre/{genre}?filter={url_filter}&page={page}",
+	Some(format!(
+		"{}/genre/all?filter={url_filter}&page={page}",
 		params.base_url
-	)
+	))
 }
 
 pub fn encode(string: String, separator: char) -> String {
@@ -131,3 +399,145 @@ pub fn encode(string: String, separator: char) -> String {
 		})
 		.collect()
 }
+
+// get the site's genre catalogue, refreshing the cached copy once it's a week old
+pub fn get_genres(params: &Params) -> Vec<Genre> {
+	let now = current_date() as i64;
+	let cache = defaults_get::<String>(GENRE_CACHE_KEY)
+		.and_then(|json| serde_json::from_str::<GenreCache>(&json).ok());
+	if let Some(cache) = cache.as_ref() {
+		if !cache.genres.is_empty() && now - cache.updated_at < GENRE_CACHE_DURATION {
+			return cache.genres.clone();
+		}
+	}
+
+	match fetch_genres(params) {
+		Ok(genres) if !genres.is_empty() => {
+			let new_cache = GenreCache {
+				updated_at: now,
+				genres,
+			};
+			if let Ok(json) = serde_json::to_string(&new_cache) {
+				defaults_set(GENRE_CACHE_KEY, DefaultValue::String(json));
+			}
+			new_cache.genres
+		}
+		// fall back to an outdated cache if the site can't be reached
+		_ => cache.map(|cache| cache.genres).unwrap_or_default(),
+	}
+}
+
+// maps genre names (e.g. from a tapped manga tag) to their ids, keeping values that are already ids
+fn resolve_genre_ids(params: &Params, values: impl IntoIterator<Item = String>) -> Vec<String> {
+	let mut genres: Option<Vec<Genre>> = None;
+	values
+		.into_iter()
+		.filter_map(|value| {
+			if value.chars().all(|c| c.is_ascii_digit()) {
+				return Some(value);
+			}
+			genres
+				.get_or_insert_with(|| get_genres(params))
+				.iter()
+				.find(|genre| genre.name.eq_ignore_ascii_case(value.trim()))
+				.map(|genre| genre.id.clone())
+		})
+		.collect()
+}
+
+// genre ids are only listed on the advanced search page
+fn fetch_genres(params: &Params) -> Result<Vec<Genre>> {
+	let html = Request::get(format!(
+		"{}{}",
+		params.base_url, params.advanced_search_path
+	))?
+	.header("Referer", &format!("{}/", params.base_url))
+	.html()?;
+
+	Ok(html
+		.select(params.genre_selector.as_ref())
+		.map(|els| {
+			els.filter_map(|el| {
+				let id = el.attr("data-i")?;
+				let name = el.attr("title").or_else(|| el.text())?;
+				Some(Genre {
+					id,
+					name: name.trim().into(),
+				})
+			})
+			.collect()
+		})
+		.unwrap_or_default())
+}
+
+// the preferred image server, starting at 1
+pub fn get_image_server() -> usize {
+	defaults_get::<String>(IMAGE_SERVER_KEY)
+		.and_then(|server| server.parse::<usize>().ok())
+		.filter(|server| *server > 0)
+		.unwrap_or(1)
+}
+
+// parses a javascript array of strings, e.g. `var cdns = ["https:\/\/..."];`
+pub fn parse_script_array(script: &str, name: &str) -> Option<Vec<String>> {
+	let start = script.find(&format!("var {name}"))?;
+	let array_start = start + script[start..].find('[')?;
+	let array_end = array_start + script[array_start..].find(']')?;
+	serde_json::from_str::<Vec<String>>(&script[array_start..=array_end]).ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use aidoku_test::aidoku_test;
+
+	#[aidoku_test]
+	fn chapter_info_volume_and_title() {
+		let info = parse_chapter_info("Vol.3 Chapter 12.5: Title", "/manga/test/chapter-12-5");
+		assert_eq!(info.volume_number, Some(3.0));
+		assert_eq!(info.chapter_number, Some(12.5));
+		assert_eq!(info.title.as_deref(), Some("Title"));
+
+		let info = parse_chapter_info("Chapter 7 - Title", "/manga/test/chapter-7");
+		assert_eq!(info.volume_number, None);
+		assert_eq!(info.chapter_number, Some(7.0));
+		assert_eq!(info.title.as_deref(), Some("Title"));
+
+		let info = parse_chapter_info("Chapter 5", "/manga/test/chapter-5");
+		assert_eq!(info.chapter_number, Some(5.0));
+		assert_eq!(info.title, None);
+	}
+
+	#[aidoku_test]
+	fn chapter_info_url_fallback() {
+		let info = parse_chapter_info("Oneshot", "/manga/test/chapter-0-2");
+		assert_eq!(info.chapter_number, Some(0.2));
+		assert_eq!(info.title.as_deref(), Some("Oneshot"));
+
+		// extras don't take the number from their url
+		let info = parse_chapter_info("Side Story 2", "/manga/test/chapter-30");
+		assert_eq!(info.chapter_number, None);
+		assert_eq!(info.title.as_deref(), Some("Side Story 2"));
+	}
+
+	#[aidoku_test]
+	fn relative_dates() {
+		let now = current_date() as i64;
+		let within = |date: Option<i64>, expected: i64| {
+			date.is_some_and(|date| (date - expected).abs() <= 5)
+		};
+		assert!(within(parse_date("just now"), now));
+		assert!(within(parse_date("2 hours ago"), now - 2 * 60 * 60));
+		assert!(within(parse_date("an hour ago"), now - 60 * 60));
+		assert!(within(parse_date("3 days ago"), now - 3 * 60 * 60 * 24));
+		assert_eq!(parse_date("3 fortnights ago"), None);
+	}
+
+	#[aidoku_test]
+	fn absolute_dates() {
+		assert!(parse_date("Jan 05,2024").is_some());
+		assert!(parse_date("Jan 05, 2024").is_some());
+		assert!(parse_date("Jan-05-2024 12:30").is_some());
+		assert_eq!(parse_date("not a date"), None);
+	}
+}
@@ -3,11 +3,11 @@ use crate::helper;
 use aidoku::{
 	alloc::{vec, String, Vec},
 	helpers::date::parse_local_date,
-	imports::{error::AidokuError, net::Request, std::send_partial_result},
+	imports::{canvas::ImageRef, error::AidokuError, net::Request, std::send_partial_result},
 	prelude::*,
-	Chapter, ContentRating, DeepLinkResult, FilterItem, FilterValue, HomeComponent,
-	HomeComponentValue, HomeLayout, Listing, Manga, MangaPageResult, MangaWithChapter, Page,
-	PageContent, PageContext, Result, Viewer,
+	Chapter, ContentRating, DeepLinkResult, Filter, FilterItem, FilterValue, HomeComponent,
+	HomeComponentValue, HomeLayout, ImageResponse, Listing, Manga, MangaPageResult,
+	MangaWithChapter, MultiSelectFilter, Page, PageContent, PageContext, Result, Viewer,
 };
 
 pub trait Impl {
@@ -21,29 +21,8 @@ pub trait Impl {
 		listing: Listing,
 		page: i32,
 	) -> Result<MangaPageResult> {
-		let (sort_index, extra_filter) = match listing.id.as_str() {
-			"new" => (0, None),
-			"latest" => (1, None),
-			"hot" => (2, None),
-			"completed" => (
-				1,
-				Some(FilterValue::Select {
-					id: "status".into(),
-					value: "Completed".into(),
-				}),
-			),
-			_ => return Err(AidokuError::message("Invalid listing")),
-		};
-
-		let mut filters = Vec::with_capacity(if extra_filter.is_some() { 2 } else { 1 });
-		filters.push(FilterValue::Sort {
-			id: "sort".into(),
-			index: sort_index,
-			ascending: false,
-		});
-		if let Some(f) = extra_filter {
-			filters.push(f);
-		}
+		let filters = helper::get_listing_filters(&listing.id)
+			.ok_or(AidokuError::message("Invalid listing"))?;
 
 		self.get_search_manga_list(params, None, page, filters)
 	}
@@ -55,40 +34,14 @@ pub trait Impl {
 		page: i32,
 		filters: Vec<FilterValue>,
 	) -> Result<MangaPageResult> {
-		let url = helper::get_search_url(params, query, page, filters);
+		let Some(url) = helper::get_search_url(params, query, page, filters) else {
+			return Ok(MangaPageResult::default());
+		};
 		let html = Request::get(url)?
 			.header("Referer", &format!("{}/", params.base_url))
 			.html()?;
 
-		let entries = html
-			.select(params.item_selector.as_ref())
-			.map(|els| {
-				els.filter_map(|item| {
-					let title = item
-						.select_first(".story_name")
-						.and_then(|el| el.text())
-						.unwrap_or_else(|| {
-							item.select_first("a")
-								.and_then(|a| a.attr("title"))
-								.unwrap_or_default()
-						});
-					let url = item.select_first("a")?.attr("href")?;
-					let key = url
-						.strip_prefix(params.base_url.as_ref())
-						.unwrap_or(&url)
-						.into();
-					let cover = item.select_first("img").and_then(|img| img.attr("src"));
-					Some(Manga {
-						key,
-						cover,
-						title,
-						url: Some(url),
-						..Default::default()
-					})
-				})
-				.collect::<Vec<_>>()
-			})
-			.unwrap_or_default();
+		let entries = helper::parse_manga_list(params, &html);
 
 		// last page link text in the format "Last(NUM)"
 		let has_next_page = {
@@ -192,17 +145,24 @@ pub trait Impl {
 							.strip_prefix(params.base_url.as_ref())
 							.unwrap_or(&url)
 							.into();
-						let title = link.text().map(helper::strip_default_chapter_title);
-						let chapter_number = helper::get_chapter_number(&url);
+						let info =
+							helper::parse_chapter_info(&link.text().unwrap_or_default(), &url);
+						// some sites render relative dates instead of a title attribute
 						let date_uploaded = el
 							.select_first("span[title]")
 							.and_then(|span| span.attr("title"))
-							.and_then(|date| parse_local_date(date, "%b-%d-%Y %H:%M"));
+							.and_then(|date| parse_local_date(date, "%b-%d-%Y %H:%M"))
+							.or_else(|| {
+								el.select("span")?
+									.filter_map(|span| span.text())
+									.find_map(|text| helper::parse_date(&text))
+							});
 
 						Some(Chapter {
 							key,
-							title,
-							chapter_number,
+							title: info.title,
+							chapter_number: info.chapter_number,
+							volume_number: info.volume_number,
 							date_uploaded,
 							url: Some(url),
 							..Default::default()
@@ -217,17 +177,72 @@ pub trait Impl {
 
 	fn get_page_list(&self, params: &Params, _manga: Manga, chapter: Chapter) -> Result<Vec<Page>> {
 		let url = format!("{}{}", params.base_url, chapter.key);
+		let server = helper::get_image_server();
 
-		let html = Request::get(url)?
+		// the selected server is remembered by the site in a cookie
+		if let Some(path) = (params.get_server_switch_path)(server) {
+			_ = Request::get(format!("{}{path}", params.base_url))
+				.map(|req| req.header("Referer", &url).send());
+		}
+
+		let html = Request::get(&url)?
 			.header("Referer", &format!("{}/", params.base_url))
 			.html()?;
 
+		// newer sites list every cdn host and the image paths in a script
+		let script_pages = html.select("script").and_then(|scripts| {
+			scripts.filter_map(|el| el.data()).find_map(|script| {
+				let cdns = helper::parse_script_array(&script, "cdns")?;
+				let images = helper::parse_script_array(&script, "chapterImages")?;
+				if cdns.is_empty() || images.is_empty() {
+					return None;
+				}
+				let cdn = &cdns[(server - 1).min(cdns.len() - 1)];
+				let backup_cdn = cdns.iter().find(|other| *other != cdn);
+				Some(
+					images
+						.iter()
+						.map(|path| {
+							let path = path.trim_start_matches('/');
+							let url = format!("{}/{path}", cdn.trim_end_matches('/'));
+							Page {
+								content: match backup_cdn {
+									Some(backup_cdn) => {
+										let mut context = PageContext::new();
+										context.insert(
+											"backup".into(),
+											format!("{}/{path}", backup_cdn.trim_end_matches('/')),
+										);
+										PageContent::url_context(url, context)
+									}
+									None => PageContent::url(url),
+								},
+								..Default::default()
+							}
+						})
+						.collect::<Vec<_>>(),
+				)
+			})
+		});
+		if let Some(pages) = script_pages {
+			return Ok(pages);
+		}
+
 		Ok(html
 			.select("div.container-chapter-reader > img")
 			.map(|els| {
 				els.filter_map(|el| {
+					let src = el.attr("src")?;
+					let backup = el.attr("data-src").filter(|backup| *backup != src);
 					Some(Page {
-						content: PageContent::url(el.attr("src")?),
+						content: match backup {
+							Some(backup) => {
+								let mut context = PageContext::new();
+								context.insert("backup".into(), backup);
+								PageContent::url_context(src, context)
+							}
+							None => PageContent::url(src),
+						},
 						..Default::default()
 					})
 				})
@@ -239,6 +254,31 @@ pub trait Impl {
 	fn get_home(&self, params: &Params) -> Result<HomeLayout> {
 		let html = Request::get(&params.base_url)?.html()?;
 
+		// completed series, filtered like the completed listing
+		let completed = helper::get_search_url(
+			params,
+			None,
+			1,
+			helper::get_listing_filters("completed").unwrap_or_default(),
+		)
+		.and_then(|url| Request::get(url).ok())
+		.and_then(|request| {
+			request
+				.header("Referer", &format!("{}/", params.base_url))
+				.html()
+				.ok()
+		})
+		.map(|html| helper::parse_manga_list(params, &html))
+		.unwrap_or_default();
+
+		fn listing(id: &str, name: &str) -> Option<Listing> {
+			Some(Listing {
+				id: id.into(),
+				name: name.into(),
+				..Default::default()
+			})
+		}
+
 		Ok(HomeLayout {
 			components: vec![
 				HomeComponent {
@@ -257,6 +297,7 @@ pub trait Impl {
 												.into(),
 											title: link.attr("title")?,
 											cover: el.select_first("img")?.attr("src"),
+											url: Some(href),
 											..Default::default()
 										}
 										.into(),
@@ -265,7 +306,7 @@ pub trait Impl {
 								.collect()
 							})
 							.unwrap_or_default(),
-						listing: None,
+						listing: listing("hot", "Hot Manga"),
 					},
 					..Default::default()
 				},
@@ -279,7 +320,15 @@ pub trait Impl {
 								els.filter_map(|el| {
 									let manga_link = el.select_first("ul > li > h3 > a")?;
 									let manga_href = manga_link.attr("href")?;
-									let chpater_link = el.select_first("ul > li > span > a")?;
+									let chapter_link = el.select_first("ul > li > span > a")?;
+									let chapter_href = chapter_link.attr("href")?;
+									let info = helper::parse_chapter_info(
+										&chapter_link
+											.text()
+											.or_else(|| chapter_link.attr("title"))
+											.unwrap_or_default(),
+										&chapter_href,
+									);
 									Some(MangaWithChapter {
 										manga: Manga {
 											key: manga_href
@@ -287,10 +336,18 @@ pub trait Impl {
 												.into(),
 											title: manga_link.text()?,
 											cover: el.select_first("img")?.attr("src"),
+											url: Some(manga_href.clone()),
 											..Default::default()
 										},
 										chapter: Chapter {
-											title: chpater_link.attr("title"),
+											key: chapter_href
+												.strip_prefix(params.base_url.as_ref())
+												.unwrap_or(&chapter_href)
+												.into(),
+											title: info.title,
+											chapter_number: info.chapter_number,
+											volume_number: info.volume_number,
+											url: Some(chapter_href),
 											..Default::default()
 										},
 									})
@@ -298,33 +355,32 @@ pub trait Impl {
 								.collect()
 							})
 							.unwrap_or_default(),
-						listing: None,
+						listing: listing("latest", "Latest Manga"),
+					},
+					..Default::default()
+				},
+				HomeComponent {
+					title: Some("Completed Manga".into()),
+					value: HomeComponentValue::Scroller {
+						entries: completed.into_iter().map(Into::into).collect(),
+						listing: listing("completed", "Completed Manga"),
 					},
 					..Default::default()
 				},
 				HomeComponent {
 					title: Some("Genres".into()),
 					value: HomeComponentValue::Filters(
-						html.select("table > tbody > tr > td > a")
-							.map(|els| {
-								els.skip(6) // sort and status items
-									.filter_map(|el| {
-										let genre = el.attr("title")?;
-										if !el.attr("href").is_some_and(|href| href.contains("genre/")) {
-											// filter out tags on kakalot
-											return None;
-										}
-										Some(FilterItem {
-											title: genre.clone(),
-											values: Some(vec![FilterValue::Select {
-												id: "genre".into(),
-												value: genre,
-											}]),
-										})
-									})
-									.collect()
+						helper::get_genres(params)
+							.into_iter()
+							.map(|genre| FilterItem {
+								title: genre.name,
+								values: Some(vec![FilterValue::MultiSelect {
+									id: "genre".into(),
+									included: vec![genre.id],
+									excluded: Vec::new(),
+								}]),
 							})
-							.unwrap_or_default(),
+							.collect(),
 					),
 					..Default::default()
 				},
@@ -332,6 +388,27 @@ pub trait Impl {
 		})
 	}
 
+	fn get_dynamic_filters(&self, params: &Params) -> Result<Vec<Filter>> {
+		let genres = helper::get_genres(params);
+		if genres.is_empty() {
+			return Ok(Vec::new());
+		}
+		let (options, ids) = genres
+			.into_iter()
+			.map(|genre| (genre.name.into(), genre.id.into()))
+			.unzip();
+		Ok(vec![MultiSelectFilter {
+			id: "genre".into(),
+			title: Some("Genres".into()),
+			is_genre: true,
+			can_exclude: true,
+			options,
+			ids: Some(ids),
+			..Default::default()
+		}
+		.into()])
+	}
+
 	fn get_image_request(
 		&self,
 		params: &Params,
@@ -341,6 +418,27 @@ pub trait Impl {
 		Ok(Request::get(url)?.header("Referer", &format!("{}/", params.base_url)))
 	}
 
+	fn process_page_image(
+		&self,
+		params: &Params,
+		response: ImageResponse,
+		context: Option<PageContext>,
+	) -> Result<ImageRef> {
+		// retry with the alternate server if the preferred one failed or sent something
+		// that isn't an image
+		let decoded = response.image.width() as usize > 0 && response.image.height() as usize > 0;
+		if response.code < 400 && decoded {
+			return Ok(response.image);
+		}
+		let Some(backup) = context.as_ref().and_then(|context| context.get("backup")) else {
+			return Ok(response.image);
+		};
+		let data = Request::get(backup)?
+			.header("Referer", &format!("{}/", params.base_url))
+			.data()?;
+		Ok(ImageRef::new(&data))
+	}
+
 	fn handle_deep_link(&self, params: &Params, url: String) -> Result<Option<DeepLinkResult>> {
 		let Some(path) = url.strip_prefix(params.base_url.as_ref()) else {
 			return Ok(None);
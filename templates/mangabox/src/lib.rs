@@ -1,13 +1,15 @@
 #![no_std]
 use aidoku::{
 	alloc::{borrow::Cow, String, Vec},
-	imports::net::Request,
-	Chapter, DeepLinkHandler, DeepLinkResult, FilterValue, Home, HomeLayout, ImageRequestProvider,
-	Listing, ListingProvider, Manga, MangaPageResult, Page, PageContext, Result, Source,
+	imports::{canvas::ImageRef, net::Request},
+	Chapter, DeepLinkHandler, DeepLinkResult, DynamicFilters, Filter, FilterValue, Home,
+	HomeLayout, ImageRequestProvider, ImageResponse, Listing, ListingProvider, Manga,
+	MangaPageResult, Page, PageContext, PageImageProcessor, Result, Source,
 };
 
 mod helper;
 mod imp;
+mod models;
 
 pub use imp::Impl;
 
@@ -15,16 +17,25 @@ pub struct Params {
 	pub base_url: Cow<'static, str>,
 	pub item_selector: Cow<'static, str>,
 	pub search_path: Cow<'static, str>,
-	pub genres: Cow<'static, [&'static str]>,
+	pub advanced_search_path: Cow<'static, str>,
+	pub author_path: Cow<'static, str>,
+	// css selector for genre items (with a data-i id attribute) on the advanced search page
+	pub genre_selector: Cow<'static, str>,
+	// path added to base url to switch the image server (starting at 1), if the site has one
+	pub get_server_switch_path: fn(usize) -> Option<String>,
 }
 
 impl Default for Params {
 	fn default() -> Self {
 		Self {
 			base_url: "".into(),
-			item_selector: ".panel_story_list .story_item, .list-truyen-item-wrap".into(),
+			item_selector:
+				".panel_story_list .story_item, .list-truyen-item-wrap, .content-genres-item".into(),
 			search_path: "/search/story".into(),
-			genres: Cow::Borrowed(&[]),
+			advanced_search_path: "/advanced_search".into(),
+			author_path: "/author".into(),
+			genre_selector: ".advanced-search-tool-genres-list span[data-i]".into(),
+			get_server_switch_path: |_| None,
 		}
 	}
 }
@@ -78,12 +89,29 @@ impl<T: Impl> Home for MangaBox<T> {
 	}
 }
 
+impl<T: Impl> DynamicFilters for MangaBox<T> {
+	fn get_dynamic_filters(&self) -> Result<Vec<Filter>> {
+		self.inner.get_dynamic_filters(&self.params)
+	}
+}
+
 impl<T: Impl> ImageRequestProvider for MangaBox<T> {
 	fn get_image_request(&self, url: String, context: Option<PageContext>) -> Result<Request> {
 		self.inner.get_image_request(&self.params, url, context)
 	}
 }
 
+impl<T: Impl> PageImageProcessor for MangaBox<T> {
+	fn process_page_image(
+		&self,
+		response: ImageResponse,
+		context: Option<PageContext>,
+	) -> Result<ImageRef> {
+		self.inner
+			.process_page_image(&self.params, response, context)
+	}
+}
+
 impl<T: Impl> DeepLinkHandler for MangaBox<T> {
 	fn handle_deep_link(&self, url: String) -> Result<Option<DeepLinkResult>> {
 		self.inner.handle_deep_link(&self.params, url)
@@ -0,0 +1,15 @@
+use aidoku::alloc::{String, Vec};
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Deserialize, Serialize, Debug, Clone)]
+pub struct Genre {
+	pub id: String,
+	pub name: String,
+}
+
+#[derive(Default, Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub struct GenreCache {
+	pub updated_at: i64,
+	pub genres: Vec<Genre>,
+}
@@ -0,0 +1,177 @@
+use aidoku::{
+	alloc::{vec, Vec},
+	imports::canvas::{Canvas, ImageRef, Rect},
+};
+
+// images marked as shuffled are split into square pieces that are shuffled
+// among the other pieces of the same size using a seeded random generator
+const PIECE_SIZE: usize = 200;
+const SEED: &str = "stay";
+
+// port of the rc4-based generator from the `seedrandom` js library used by the reader
+struct SeedRandom {
+	i: u8,
+	j: u8,
+	s: [u8; 256],
+}
+
+impl SeedRandom {
+	fn new(seed: &str) -> Self {
+		// mix the seed string into a key
+		let mut key: Vec<u32> = Vec::new();
+		let mut smear: u32 = 0;
+		for (j, c) in seed.encode_utf16().enumerate() {
+			let idx = j & 0xff;
+			smear ^= key.get(idx).copied().unwrap_or(0).wrapping_mul(19);
+			let value = smear.wrapping_add(c as u32) & 0xff;
+			if idx < key.len() {
+				key[idx] = value;
+			} else {
+				key.push(value);
+			}
+		}
+		if key.is_empty() {
+			key.push(0);
+		}
+
+		let mut s = [0u8; 256];
+		for (i, value) in s.iter_mut().enumerate() {
+			*value = i as u8;
+		}
+		let mut j: u8 = 0;
+		for i in 0..256 {
+			let t = s[i];
+			j = j.wrapping_add(key[i % key.len()] as u8).wrapping_add(t);
+			s[i] = s[j as usize];
+			s[j as usize] = t;
+		}
+
+		let mut random = Self { i: 0, j: 0, s };
+		// the first 256 bytes are discarded
+		for _ in 0..256 {
+			random.next_byte();
+		}
+		random
+	}
+
+	fn next_byte(&mut self) -> u8 {
+		self.i = self.i.wrapping_add(1);
+		let t = self.s[self.i as usize];
+		self.j = self.j.wrapping_add(t);
+		self.s[self.i as usize] = self.s[self.j as usize];
+		self.s[self.j as usize] = t;
+		self.s[self.s[self.i as usize].wrapping_add(t) as usize]
+	}
+
+	fn next_bytes(&mut self, count: usize) -> f64 {
+		(0..count).fold(0.0, |r, _| r * 256.0 + self.next_byte() as f64)
+	}
+
+	fn next_f64(&mut self) -> f64 {
+		const START_DENOM: f64 = 281474976710656.0; // 256^6
+		const SIGNIFICANCE: f64 = 4503599627370496.0; // 2^52
+		const OVERFLOW: f64 = 9007199254740992.0; // 2^53
+
+		let mut n = self.next_bytes(6);
+		let mut d = START_DENOM;
+		let mut x: u64 = 0;
+		while n < SIGNIFICANCE {
+			n = (n + x as f64) * 256.0;
+			d *= 256.0;
+			x = self.next_bytes(1) as u64;
+		}
+		while n >= OVERFLOW {
+			n /= 2.0;
+			d /= 2.0;
+			x >>= 1;
+		}
+		(n + x as f64) / d
+	}
+}
+
+// piece positions grouped by piece size, keeping the order they appear in
+fn piece_groups(width: usize, height: usize) -> Vec<((usize, usize), Vec<(usize, usize)>)> {
+	let mut groups: Vec<((usize, usize), Vec<(usize, usize)>)> = Vec::new();
+	for y in (0..height).step_by(PIECE_SIZE) {
+		for x in (0..width).step_by(PIECE_SIZE) {
+			let size = (PIECE_SIZE.min(width - x), PIECE_SIZE.min(height - y));
+			match groups.iter_mut().find(|(s, _)| *s == size) {
+				Some((_, pieces)) => pieces.push((x, y)),
+				None => groups.push((size, vec![(x, y)])),
+			}
+		}
+	}
+	groups
+}
+
+// the original index of each piece in a group, in the order the pieces are stored.
+// each group is shuffled with a freshly seeded generator
+fn piece_order(count: usize) -> Vec<usize> {
+	let mut random = SeedRandom::new(SEED);
+	let mut indices = (0..count).collect::<Vec<_>>();
+	(0..count)
+		.map(|_| indices.remove((random.next_f64() * indices.len() as f64) as usize))
+		.collect()
+}
+
+pub fn unscramble_image(image: &ImageRef) -> ImageRef {
+	let width = image.width() as usize;
+	let height = image.height() as usize;
+
+	let mut canvas = Canvas::new(width as f32, height as f32);
+	for ((w, h), pieces) in piece_groups(width, height) {
+		for (&(src_x, src_y), original) in pieces.iter().zip(piece_order(pieces.len())) {
+			let (dst_x, dst_y) = pieces[original];
+			canvas.copy_image(
+				image,
+				Rect::new(src_x as f32, src_y as f32, w as f32, h as f32),
+				Rect::new(dst_x as f32, dst_y as f32, w as f32, h as f32),
+			);
+		}
+	}
+	canvas.get_image()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use aidoku_test::aidoku_test;
+
+	// expected values are from the seedrandom js library
+	#[aidoku_test]
+	fn seeded_sequence() {
+		let mut random = SeedRandom::new("hello.");
+		assert_eq!(random.next_f64(), 0.9282578795792454);
+
+		let mut random = SeedRandom::new(SEED);
+		for expected in [
+			0.07111011901119368,
+			0.6523642568980361,
+			0.8556215202976776,
+			0.6511056873131681,
+			0.1549193570060695,
+		] {
+			assert_eq!(random.next_f64(), expected);
+		}
+	}
+
+	#[aidoku_test]
+	fn tile_permutation() {
+		assert_eq!(piece_order(6), [0, 4, 5, 2, 1, 3]);
+		assert_eq!(piece_order(1), [0]);
+		assert!(piece_order(0).is_empty());
+	}
+
+	#[aidoku_test]
+	fn pieces_grouped_by_size() {
+		assert_eq!(
+			piece_groups(450, 250),
+			[
+				((200, 200), vec![(0, 0), (200, 0)]),
+				((50, 200), vec![(400, 0)]),
+				((200, 50), vec![(0, 200), (200, 200)]),
+				((50, 50), vec![(400, 200)]),
+			]
+		);
+	}
+}
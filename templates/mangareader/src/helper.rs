@@ -1,4 +1,129 @@
-use aidoku::{alloc::String, imports::html::Element};
+use crate::Params;
+use aidoku::{
+	alloc::{String, Vec},
+	helpers::date::parse_local_date,
+	imports::{defaults::defaults_get, html::Element, std::current_date},
+};
+
+const LANGUAGES_KEY: &str = "languages";
+const SHOW_VOLUMES_KEY: &str = "showVolumes";
+
+// the chapter languages selected in settings, limited to the ones the site offers
+pub fn get_languages(params: &Params) -> Vec<&'static str> {
+	let selected = defaults_get::<Vec<String>>(LANGUAGES_KEY).unwrap_or_default();
+	let languages = params
+		.languages
+		.iter()
+		.copied()
+		.filter(|lang| selected.iter().any(|s| s == lang))
+		.collect::<Vec<_>>();
+	if languages.is_empty() {
+		params.languages.first().copied().into_iter().collect()
+	} else {
+		languages
+	}
+}
+
+pub fn show_volumes() -> bool {
+	defaults_get::<bool>(SHOW_VOLUMES_KEY).unwrap_or(true)
+}
+
+// parses chapter release dates, either relative ("2 days ago", "3日前") or absolute ("Jan 05, 2024", "2024/01/05")
+pub fn parse_date(text: &str) -> Option<i64> {
+	let text = text.trim();
+	let lowercase_text = text.to_lowercase();
+	let now = current_date() as i64;
+
+	if lowercase_text == "just now" || text == "たった今" {
+		return Some(now);
+	}
+	if lowercase_text == "yesterday" || text == "昨日" {
+		return Some(now - 60 * 60 * 24);
+	}
+
+	let relative = lowercase_text
+		.strip_suffix("ago")
+		.or_else(|| lowercase_text.strip_suffix('前'));
+	if let Some(relative) = relative {
+		let relative = relative.trim();
+		let split = relative
+			.find(|c: char| !c.is_ascii_digit())
+			.unwrap_or(relative.len());
+		let (amount, unit) = relative.split_at(split);
+		let amount = match amount {
+			"" if unit.starts_with("a ") || unit.starts_with("an ") => 1,
+			amount => amount.parse::<i64>().ok()?,
+		};
+		let unit = unit
+			.trim_start_matches("an ")
+			.trim_start_matches("a ")
+			.trim();
+		let seconds = if unit.starts_with("sec") || unit.starts_with('秒') {
+			1
+		} else if unit.starts_with("min") || unit.starts_with('分') {
+			60
+		} else if unit.starts_with("hour") || unit.starts_with("時間") {
+			60 * 60
+		} else if unit.starts_with("day") || unit.starts_with('日') {
+			60 * 60 * 24
+		} else if unit.starts_with("week") || unit.starts_with("週間") {
+			60 * 60 * 24 * 7
+		} else if unit.starts_with("month") || unit.starts_with("ヶ月") || unit.starts_with("か月")
+		{
+			60 * 60 * 24 * 30
+		} else if unit.starts_with("year") || unit.starts_with('年') {
+			60 * 60 * 24 * 365
+		} else {
+			return None;
+		};
+		return Some(now - amount * seconds);
+	}
+
+	[
+		"%b %d, %Y",
+		"%B %d, %Y",
+		"%Y-%m-%d",
+		"%Y/%m/%d",
+		"%Y年%m月%d日",
+	]
+	.iter()
+	.find_map(|format| parse_local_date(text, format))
+}
+
+// splits a leading volume marker ("Vol. 2", "Volume 2", "第2巻") from a chapter name
+pub fn split_volume(text: &str) -> (Option<f32>, &str) {
+	let trimmed = text.trim_start();
+	let lowercase = trimmed.to_lowercase();
+	let prefix_len = ["volume", "vol.", "vol"]
+		.iter()
+		.find(|prefix| lowercase.starts_with(*prefix))
+		.map(|prefix| prefix.len())
+		.or_else(|| trimmed.starts_with('第').then(|| '第'.len_utf8()));
+	let Some(prefix_len) = prefix_len else {
+		return (None, text);
+	};
+	let rest = trimmed[prefix_len..].trim_start();
+	let number_len = rest
+		.find(|c: char| !c.is_ascii_digit() && c != '.')
+		.unwrap_or(rest.len());
+	let Some(volume) = rest[..number_len].parse::<f32>().ok() else {
+		return (None, text);
+	};
+	let rest = &rest[number_len..];
+	// japanese volumes end in 巻, anything else (e.g. 話) means this wasn't a volume
+	let rest = if trimmed.starts_with('第') {
+		match rest.strip_prefix('巻') {
+			Some(rest) => rest,
+			None => return (None, text),
+		}
+	} else {
+		rest
+	};
+	(
+		Some(volume),
+		rest.trim_start_matches(|c: char| c.is_whitespace() || c == '-' || c == ','),
+	)
+}
 
 pub trait ElementImageAttr {
 	fn img_attr(&self) -> Option<String>;
@@ -13,3 +138,49 @@ impl ElementImageAttr for Element {
 			.or_else(|| self.attr("data-url"))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use aidoku_test::aidoku_test;
+
+	#[aidoku_test]
+	fn relative_dates() {
+		let now = current_date() as i64;
+		let within = |date: Option<i64>, expected: i64| {
+			date.is_some_and(|date| (date - expected).abs() <= 5)
+		};
+		assert!(within(parse_date("2 days ago"), now - 2 * 60 * 60 * 24));
+		assert!(within(parse_date("an hour ago"), now - 60 * 60));
+		assert!(within(parse_date("3日前"), now - 3 * 60 * 60 * 24));
+		assert!(within(parse_date("5時間前"), now - 5 * 60 * 60));
+		assert!(within(parse_date("昨日"), now - 60 * 60 * 24));
+	}
+
+	#[aidoku_test]
+	fn absolute_dates() {
+		assert!(parse_date("Jan 05, 2024").is_some());
+		assert!(parse_date("2024/01/05").is_some());
+		assert!(parse_date("2024年01月05日").is_some());
+		// day/month order is ambiguous, so it isn't guessed
+		assert_eq!(parse_date("05/01/2024"), None);
+	}
+
+	#[aidoku_test]
+	fn split_volume_prefixes() {
+		assert_eq!(split_volume("Vol. 2 Chapter 10"), (Some(2.0), "Chapter 10"));
+		assert_eq!(
+			split_volume("Volume 3 - Chapter 12"),
+			(Some(3.0), "Chapter 12")
+		);
+		assert_eq!(split_volume("vol1.5, Extra"), (Some(1.5), "Extra"));
+		assert_eq!(split_volume("第2巻 第10話"), (Some(2.0), "第10話"));
+	}
+
+	#[aidoku_test]
+	fn split_volume_without_volume() {
+		assert_eq!(split_volume("Chapter 10"), (None, "Chapter 10"));
+		assert_eq!(split_volume("第10話"), (None, "第10話"));
+		assert_eq!(split_volume("Volcano"), (None, "Volcano"));
+	}
+}
@@ -1,10 +1,9 @@
-use super::{helper::ElementImageAttr, parser, Params};
+use super::{descramble, helper::ElementImageAttr, parser, Params};
 use aidoku::{
 	alloc::{borrow::Cow, String, Vec},
 	helpers::uri::{encode_uri_component, QueryParameters},
 	imports::{
 		canvas::ImageRef,
-		error::AidokuError,
 		html::{Element, Html},
 		net::Request,
 		std::send_partial_result,
@@ -40,39 +39,68 @@ pub trait Impl {
 		page: i32,
 		filters: Vec<FilterValue>,
 	) -> Result<MangaPageResult> {
-		let url = if let Some(query) = query {
-			format!(
+		let has_filters = filters.iter().any(|filter| match filter {
+			FilterValue::Sort { index, .. } => *index != 0,
+			FilterValue::Text { value, .. } | FilterValue::Select { value, .. } => {
+				!value.is_empty()
+			}
+			FilterValue::MultiSelect {
+				included, excluded, ..
+			} => !included.is_empty() || !excluded.is_empty(),
+			_ => false,
+		});
+		let url = match query {
+			// the search page ignores filters, so only use it when the filter page can't take a keyword
+			Some(query) if params.filter_keyword_param.is_none() || !has_filters => format!(
 				"{}{}?{}={}&{}={page}",
 				params.base_url,
 				params.search_path,
 				params.search_param,
 				encode_uri_component(query),
 				params.page_param
-			)
-		} else {
-			let mut qs = QueryParameters::new();
-			(params.set_default_filters)(&mut qs);
-			for filter in filters {
-				match filter {
-					FilterValue::Sort { index, .. } => {
-						qs.set("sort", Some(self.get_sort_id(index).as_ref()));
-					}
-					FilterValue::Select { id, value } => {
-						qs.set(&id, Some(&value));
-					}
-					// genres
-					FilterValue::MultiSelect { included, .. } => {
-						qs.set("genres", Some(&included.join(",")));
+			),
+			query => {
+				let mut qs = QueryParameters::new();
+				(params.set_default_filters)(&mut qs);
+				if let (Some(query), Some(keyword_param)) = (query, &params.filter_keyword_param) {
+					qs.set(keyword_param, Some(&query));
+				}
+				for filter in filters {
+					match filter {
+						FilterValue::Sort { index, .. } => {
+							qs.set("sort", Some(self.get_sort_id(index).as_ref()));
+						}
+						// year, month, etc.
+						FilterValue::Text { id, value } | FilterValue::Select { id, value } => {
+							if !value.is_empty() {
+								qs.set(&id, Some(&value));
+							}
+						}
+						// genres
+						FilterValue::MultiSelect {
+							id,
+							included,
+							excluded,
+						} => {
+							if !included.is_empty() {
+								qs.set(&id, Some(&included.join(",")));
+							}
+							if let Some(exclude_param) = &params.genre_exclude_param {
+								if !excluded.is_empty() {
+									qs.set(exclude_param, Some(&excluded.join(",")));
+								}
+							}
+						}
+						_ => {}
 					}
-					_ => {}
 				}
+				format!(
+					"{}/filter?{}={page}{}{qs}",
+					params.base_url,
+					params.page_param,
+					if !qs.is_empty() { "&" } else { "" }
+				)
 			}
-			format!(
-				"{}/filter?{}={page}{}{qs}",
-				params.base_url,
-				params.page_param,
-				if !qs.is_empty() { "&" } else { "" }
-			)
 		};
 		let html = Request::get(&url)?.html()?;
 
@@ -109,7 +137,11 @@ pub trait Impl {
 		}
 
 		if needs_chapters {
-			manga.chapters = parser::parse_manga_chapters(&html, params)
+			let mut chapters = parser::parse_manga_chapters(&html, params);
+			if let Some(chapters) = chapters.as_mut() {
+				fetch_chapter_dates(params, chapters);
+			}
+			manga.chapters = chapters;
 		}
 
 		Ok(manga)
@@ -135,7 +167,13 @@ pub trait Impl {
 			.map(|pos| (&chapter.key[..pos]).into())
 			.unwrap_or(chapter.key);
 
-		let url = format!("{}{}", params.base_url, (params.get_page_url_path)(&id));
+		// volumes are read through a separate endpoint from chapters
+		let page_url_path = if chapter_key_without_id.contains("/volume-") {
+			(params.get_volume_page_url_path)(&id)
+		} else {
+			(params.get_page_url_path)(&id)
+		};
+		let url = format!("{}{page_url_path}", params.base_url);
 		let json = Request::get(url)?
 			.header("Accept", "application/json, text/javascript, */*; q=0.01")
 			.header(
@@ -384,10 +422,14 @@ pub trait Impl {
 	fn process_page_image(
 		&self,
 		_params: &Params,
-		_response: ImageResponse,
-		_context: Option<PageContext>,
+		response: ImageResponse,
+		context: Option<PageContext>,
 	) -> Result<ImageRef> {
-		Err(AidokuError::Unimplemented)
+		if context.is_some_and(|context| context.get("shuffled").is_some_and(|v| v == "1")) {
+			Ok(descramble::unscramble_image(&response.image))
+		} else {
+			Ok(response.image)
+		}
 	}
 
 	fn handle_deep_link(&self, params: &Params, url: String) -> Result<Option<DeepLinkResult>> {
@@ -395,19 +437,59 @@ pub trait Impl {
 			return Ok(None);
 		};
 
-		const READ_PATH: &str = "read/";
+		const READ_PATH: &str = "/read/";
 
-		if path.starts_with(READ_PATH) {
+		if let Some(read_path) = path.strip_prefix(READ_PATH) {
 			// ex: https://mangareader.to/read/the-weakest-job-becomes-the-strongest-in-the-world-with-past-life-knowledge-67999/en/chapter-2
-			let end = path.find('/').unwrap_or(path.len());
-			let manga_key = &path[..end];
-			Ok(Some(DeepLinkResult::Chapter {
-				manga_key: manga_key.into(),
-				key: path.into(),
-			}))
+			let end = read_path.find('/').unwrap_or(read_path.len());
+			let manga_key = format!("/{}", &read_path[..end]);
+			// chapter keys include the reading id when the chapter list provides one
+			let mut key: String = path.into();
+			if let Some(id) = Request::get(&url)?
+				.html()
+				.ok()
+				.and_then(|html| html.select_first("div[data-reading-id]"))
+				.and_then(|el| el.attr("data-reading-id"))
+			{
+				key.push_str(&format!("#{id}"));
+			}
+			Ok(Some(DeepLinkResult::Chapter { manga_key, key }))
 		} else {
 			// ex: https://mangareader.to/the-weakest-job-becomes-the-strongest-in-the-world-with-past-life-knowledge-67999
 			Ok(Some(DeepLinkResult::Manga { key: path.into() }))
 		}
 	}
 }
+
+// chapters that are checked for a release date on their reader page
+const DATE_FETCH_LIMIT: usize = 10;
+
+// sites that don't show dates in the chapter list may show them on the reader page,
+// so the newest chapters are dated from there
+fn fetch_chapter_dates(params: &Params, chapters: &mut [Chapter]) {
+	if chapters
+		.iter()
+		.any(|chapter| chapter.date_uploaded.is_some())
+	{
+		return;
+	}
+	let count = chapters.len().min(DATE_FETCH_LIMIT);
+	let Ok(requests) = chapters[..count]
+		.iter()
+		.map(|chapter| {
+			let path = chapter.key.split('#').next().unwrap_or_default();
+			Request::get(format!("{}{path}", params.base_url))
+		})
+		.collect::<core::result::Result<Vec<_>, _>>()
+	else {
+		return;
+	};
+	let responses = Request::send_all(requests);
+	for (chapter, response) in chapters.iter_mut().zip(responses) {
+		chapter.date_uploaded = response
+			.ok()
+			.and_then(|response| response.get_html().ok())
+			.and_then(|html| html.select_first("body"))
+			.and_then(|body| parser::parse_chapter_date(&body));
+	}
+}
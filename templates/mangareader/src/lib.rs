@@ -2,13 +2,14 @@
 use aidoku::{
 	alloc::{borrow::Cow, String, Vec},
 	helpers::uri::QueryParameters,
-	imports::{canvas::ImageRef, html::Element, net::Request},
+	imports::{canvas::ImageRef, net::Request},
 	prelude::*,
 	Chapter, DeepLinkHandler, DeepLinkResult, FilterValue, Home, HomeLayout, ImageRequestProvider,
 	ImageResponse, Listing, ListingProvider, Manga, MangaPageResult, Page, PageContext,
 	PageImageProcessor, Result, Source,
 };
 
+mod descramble;
 mod helper;
 mod imp;
 pub mod parser;
@@ -21,12 +22,20 @@ pub struct Params {
 	pub search_param: Cow<'static, str>,
 	pub page_param: Cow<'static, str>,
 	pub page_selector: Cow<'static, str>,
-	// css selector for chapter list items (typically contained in #{lang}-chapters or #{lang}-chaps)
-	pub get_chapter_selector: fn() -> Cow<'static, str>,
-	// the language of a chapter
-	pub get_chapter_language: fn(&Element) -> String,
+	// query parameter for a keyword on the filter page, if the site allows combining it with filters
+	pub filter_keyword_param: Option<Cow<'static, str>>,
+	// query parameter for excluded genres on the filter page, if the site supports exclusion
+	pub genre_exclude_param: Option<Cow<'static, str>>,
+	// languages the site has chapter lists for, the first being the default
+	pub languages: Cow<'static, [&'static str]>,
+	// css selector for a language's chapter list items (typically contained in #{lang}-chapters or #{lang}-chaps)
+	pub get_chapter_selector: fn(&str) -> String,
+	// css selector for a language's volume list items (typically contained in #{lang}-volumes), if the site has them
+	pub get_volume_selector: fn(&str) -> Option<String>,
 	// path added to base url for page list ajax request
 	pub get_page_url_path: fn(&str) -> String,
+	// path added to base url for a volume's page list ajax request
+	pub get_volume_page_url_path: fn(&str) -> String,
 	pub set_default_filters: fn(&mut QueryParameters) -> (),
 }
 
@@ -38,9 +47,17 @@ impl Default for Params {
 			search_param: "keyword".into(),
 			page_param: "page".into(),
 			page_selector: ".container-reader-chapter > div > img".into(),
-			get_chapter_selector: || "#en-chapters > li".into(),
-			get_chapter_language: |_| "en".into(),
-			get_page_url_path: |chapter_id| format!("//ajax/image/list/{chapter_id}?mode=vertical"),
+			filter_keyword_param: Some("keyword".into()),
+			genre_exclude_param: None,
+			languages: Cow::Borrowed(&["en"]),
+			get_chapter_selector: |lang| format!("#{lang}-chapters > li"),
+			get_volume_selector: |lang| Some(format!("#{lang}-volumes .item")),
+			get_page_url_path: |chapter_id| {
+				format!("/ajax/image/list/chap/{chapter_id}?mode=vertical&quality=high")
+			},
+			get_volume_page_url_path: |volume_id| {
+				format!("/ajax/image/list/vol/{volume_id}?mode=vertical&quality=high")
+			},
 			set_default_filters: |_| {},
 		}
 	}
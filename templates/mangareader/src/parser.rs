@@ -1,7 +1,10 @@
-use crate::{helper::ElementImageAttr, Params};
+use crate::{
+	helper::{self, ElementImageAttr},
+	Params,
+};
 use aidoku::{
 	alloc::{borrow::ToOwned, String, Vec},
-	imports::html::Document,
+	imports::html::{Document, Element},
 	prelude::*,
 	AidokuError, Chapter, ContentRating, Manga, MangaStatus, Result, Viewer,
 };
@@ -118,59 +121,134 @@ pub fn parse_manga_details(manga: &mut Manga, html: &Document) -> Result<()> {
 }
 
 pub fn parse_manga_chapters(html: &Document, params: &Params) -> Option<Vec<Chapter>> {
-	html.select((params.get_chapter_selector)()).map(|els| {
-		let mut c = els
-			.filter_map(|el| {
-				let link = el.select_first("a")?;
-				let url = link.attr("abs:href")?;
-				let mut key: String = url.strip_prefix(params.base_url.as_ref())?.into();
-				if let Some(id) = el.attr("data-id") {
-					key.push_str(&format!("#{id}"));
-				}
-				let mut title = link.select_first(".name").and_then(|el| el.text());
-				let chapter_number =
-					title
-						.as_ref()
-						.and_then(|title| title.find(':'))
-						.and_then(|colon| {
-							let chapter_num_text = &title.as_ref().unwrap()[..colon].to_owned();
-							title = Some(title.as_ref().unwrap()[colon + 1..].trim().into());
-							chapter_num_text
-								.chars()
-								.filter(|c| c.is_ascii_digit() || *c == '.')
-								.collect::<String>()
-								.parse::<f32>()
-								.ok()
-						});
-				if title.as_ref().is_some_and(|t| {
-					*t == format!("Chapter {}", chapter_number.unwrap_or_default())
-						|| *t == format!("第{}話", chapter_number.unwrap_or_default())
-						|| *t == format!("第 {} 話", chapter_number.unwrap_or_default())
-						|| *t == format!("【第 {} 話】", chapter_number.unwrap_or_default())
-				}) {
-					title = None;
-				}
-				let language = (params.get_chapter_language)(&el);
-				Some(Chapter {
-					key,
-					title,
-					chapter_number,
-					url: Some(url),
-					language: language.into(),
-					..Default::default()
-				})
+	let show_volumes = helper::show_volumes();
+	let mut found_list = false;
+	let mut c = Vec::new();
+	for lang in helper::get_languages(params) {
+		if let Some(els) = html.select((params.get_chapter_selector)(lang)) {
+			found_list = true;
+			c.extend(els.filter_map(|el| parse_chapter(&el, params, lang)));
+		}
+		if !show_volumes {
+			continue;
+		}
+		if let Some(els) = (params.get_volume_selector)(lang).and_then(|sel| html.select(sel)) {
+			found_list = true;
+			c.extend(els.filter_map(|el| parse_volume(&el, params, lang)));
+		}
+	}
+	if !found_list {
+		return None;
+	}
+	// sort combined chapters by chapter number, with volumes after chapters
+	// since separate languages are grouped together by default
+	c.sort_by(|a, b| {
+		let a_num = a.chapter_number.unwrap_or(-1.0);
+		let b_num = b.chapter_number.unwrap_or(-1.0);
+		let a_vol = a.volume_number.unwrap_or(-1.0);
+		let b_vol = b.volume_number.unwrap_or(-1.0);
+		b_num
+			.partial_cmp(&a_num)
+			.unwrap_or(core::cmp::Ordering::Equal)
+			.then_with(|| {
+				b_vol
+					.partial_cmp(&a_vol)
+					.unwrap_or(core::cmp::Ordering::Equal)
 			})
-			.collect::<Vec<_>>();
-		// sort combined chapters by chapter number
-		// since separate languages are grouped together by default
-		c.sort_by(|a, b| {
-			let a_num = a.chapter_number.unwrap_or(-1.0);
-			let b_num = b.chapter_number.unwrap_or(-1.0);
-			b_num
-				.partial_cmp(&a_num)
-				.unwrap_or(core::cmp::Ordering::Equal)
+	});
+	Some(c)
+}
+
+fn parse_chapter(el: &Element, params: &Params, lang: &str) -> Option<Chapter> {
+	let link = el.select_first("a")?;
+	let url = link.attr("abs:href")?;
+	let mut key: String = url.strip_prefix(params.base_url.as_ref())?.into();
+	if let Some(id) = el.attr("data-id") {
+		key.push_str(&format!("#{id}"));
+	}
+	let name = link.select_first(".name").and_then(|el| el.text());
+	let (volume_number, mut title) = match name.as_deref().map(helper::split_volume) {
+		Some((volume, rest)) => (volume, Some(String::from(rest))),
+		None => (None, None),
+	};
+	let mut chapter_number = title
+		.as_ref()
+		.and_then(|title| title.find(':'))
+		.and_then(|colon| {
+			let chapter_num_text = &title.as_ref().unwrap()[..colon].to_owned();
+			title = Some(title.as_ref().unwrap()[colon + 1..].trim().into());
+			chapter_num_text
+				.chars()
+				.filter(|c| c.is_ascii_digit() || *c == '.')
+				.collect::<String>()
+				.parse::<f32>()
+				.ok()
 		});
-		c
+	if chapter_number.is_none() {
+		chapter_number = el
+			.attr("data-number")
+			.and_then(|number| number.trim().parse::<f32>().ok());
+	}
+	if title.as_ref().is_some_and(|t| {
+		t.is_empty()
+			|| *t == format!("Chapter {}", chapter_number.unwrap_or_default())
+			|| *t == format!("第{}話", chapter_number.unwrap_or_default())
+			|| *t == format!("第 {} 話", chapter_number.unwrap_or_default())
+			|| *t == format!("【第 {} 話】", chapter_number.unwrap_or_default())
+	}) {
+		title = None;
+	}
+	let date_uploaded = parse_chapter_date(el);
+	Some(Chapter {
+		key,
+		title,
+		chapter_number,
+		volume_number,
+		date_uploaded,
+		url: Some(url),
+		language: Some(lang.into()),
+		..Default::default()
+	})
+}
+
+// release dates shown in a chapter list item or on a chapter's reader page
+pub fn parse_chapter_date(el: &Element) -> Option<i64> {
+	el.select_first("time[datetime]")
+		.and_then(|el| el.attr("datetime"))
+		// only the date portion of iso timestamps is needed
+		.map(|date| date.split('T').next().unwrap_or_default().into())
+		.or_else(|| {
+			el.select_first(".item-time, .chapter-time, .chapter-date")
+				.and_then(|el| el.text())
+		})
+		.and_then(|date| helper::parse_date(&date))
+}
+
+// volumes are listed as cover items linking to the volume reader, e.g. "/read/{slug}/en/volume-3"
+fn parse_volume(el: &Element, params: &Params, lang: &str) -> Option<Chapter> {
+	let link = el.select_first("a")?;
+	let url = link.attr("abs:href")?;
+	let mut key: String = url.strip_prefix(params.base_url.as_ref())?.into();
+	if let Some(id) = el.attr("data-id") {
+		key.push_str(&format!("#{id}"));
+	}
+	let volume_text = el
+		.select_first(".tick-vol, .manga-name")
+		.and_then(|el| el.text())
+		.unwrap_or_else(|| url.rsplit('/').next().unwrap_or_default().into());
+	let volume_number = volume_text
+		.chars()
+		.filter(|c| c.is_ascii_digit() || *c == '.')
+		.collect::<String>()
+		.parse::<f32>()
+		.ok();
+	Some(Chapter {
+		key,
+		volume_number,
+		thumbnail: el.select_first("img").and_then(|img| img.img_attr()),
+		url: Some(url),
+		language: Some(lang.into()),
+		..Default::default()
 	})
 }
 